@@ -0,0 +1,193 @@
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use nt_client::{NTAddr, NewClientOptions};
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+
+use crate::export::Format;
+use crate::value::NtType;
+
+/// Terminal dashboard for NetworkTables 4
+#[derive(Parser)]
+#[command(version, about)]
+// Exporting a recording doesn't need a server; the other subcommands check for one themselves
+#[command(subcommand_negates_reqs = true)]
+#[command(group(ArgGroup::new("source").required(true).args(["address", "team", "replay"])))]
+pub struct Cli {
+    /// Server to connect to: an IPv4 address, `localhost`, or a team number
+    #[arg(short, long, env = "DASH89_ADDRESS", value_parser = parse_address)]
+    pub address: Option<NTAddr>,
+
+    /// Team number, connecting to the robot at 10.TE.AM.2
+    #[arg(short, long, env = "DASH89_TEAM")]
+    pub team: Option<u16>,
+
+    /// Replay a .wpilog file instead of connecting to a server
+    #[arg(long, env = "DASH89_REPLAY")]
+    pub replay: Option<PathBuf>,
+
+    /// Port of the NT4 server, if it isn't the default 5810
+    #[arg(short, long, env = "DASH89_PORT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub port: Option<u16>,
+
+    /// Config file to use instead of the one in the user's config directory
+    #[arg(short, long, env = "DASH89_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// File to write log messages to, in addition to the console tab
+    #[arg(long, env = "DASH89_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Lowest level of log messages to keep
+    #[arg(long, env = "DASH89_LOG_LEVEL", value_enum, default_value_t = LogLevel::Info)]
+    pub log_level: LogLevel,
+
+    /// Never publish values to the server
+    #[arg(long, env = "DASH89_READ_ONLY")]
+    pub read_only: bool,
+
+    /// Record NT traffic to this .wpilog file from startup
+    #[arg(long, env = "DASH89_RECORD")]
+    pub record: Option<PathBuf>,
+
+    /// Directory for recordings started from the dashboard
+    #[arg(long, env = "DASH89_RECORD_DIR", default_value = ".")]
+    pub record_dir: PathBuf,
+
+    /// Print JSON lines instead of plain text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Seconds to wait for the server before giving up
+    #[arg(long, global = true, default_value_t = 5)]
+    pub timeout: u64,
+
+    /// Run a single command instead of the dashboard
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Exit codes: 0 on success, 1 if the topic doesn't exist or nothing matched, 2 for bad input,
+/// 3 if the server couldn't be reached or didn't take a published value, 4 if updates were
+/// dropped and the output would be incomplete
+#[derive(Subcommand)]
+pub enum Command {
+    /// List announced topics and their types
+    List {
+        /// Only list topics starting with this prefix
+        prefix: Option<String>,
+    },
+    /// Print the current value of a topic
+    Get { topic: String },
+    /// Publish a value to a topic
+    Set {
+        topic: String,
+        value: String,
+        /// Type to publish as when the topic isn't announced yet, e.g. `double` or `string[]`
+        #[arg(long = "type", value_parser = parse_type)]
+        ty: Option<NtType>,
+    },
+    /// Print values of topics under a prefix as they change, until interrupted
+    Watch { prefix: String },
+    /// Export values from a .wpilog recording as CSV or JSON lines
+    Export(ExportArgs),
+}
+
+impl Command {
+    pub fn needs_server(&self) -> bool {
+        !matches!(self, Command::Export(_))
+    }
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Recording to read
+    pub log: PathBuf,
+
+    /// Topic to export; can be repeated
+    #[arg(long = "topic")]
+    pub topics: Vec<String>,
+
+    /// Export every topic under this prefix; can be repeated
+    #[arg(long = "prefix")]
+    pub prefixes: Vec<String>,
+
+    /// Skip values before this many seconds into the recording
+    #[arg(long)]
+    pub from: Option<f64>,
+
+    /// Skip values after this many seconds into the recording
+    #[arg(long)]
+    pub to: Option<f64>,
+
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    pub format: Format,
+
+    /// File to write to instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+impl Cli {
+    pub fn client_options(&self) -> NewClientOptions {
+        let addr = match (&self.address, self.team) {
+            (Some(addr), _) => addr.clone(),
+            (None, Some(team)) => NTAddr::TeamNumber(team),
+            // Replaying, so there's no server
+            (None, None) => NTAddr::Local,
+        };
+        NewClientOptions {
+            addr,
+            unsecure_port: self.port,
+            ..Default::default()
+        }
+    }
+}
+
+fn parse_address(input: &str) -> Result<NTAddr, String> {
+    if input == "localhost" {
+        return Ok(NTAddr::Local);
+    }
+    if let Ok(team) = input.parse::<u16>() {
+        return Ok(NTAddr::TeamNumber(team));
+    }
+    input.parse::<Ipv4Addr>().map(NTAddr::Custom).map_err(|_| {
+        format!(
+            "'{}' is not an IPv4 address, `localhost`, or a team number",
+            input
+        )
+    })
+}
+
+fn parse_type(input: &str) -> Result<NtType, String> {
+    match NtType::from_type_str(input) {
+        NtType::Raw | NtType::Other(_) => Err(format!(
+            "'{}' is not a publishable type (boolean, int, float, double, string, json, or an array of those)",
+            input
+        )),
+        ty => Ok(ty),
+    }
+}
@@ -4,18 +4,77 @@ use std::path::PathBuf;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
+    /// Where the config was loaded from and is saved back to
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    pub grid: GridConfig,
+    #[serde(default)]
+    pub tabs: Vec<Tab>,
+    // Old single-grid configs kept their widgets here; they get moved into a tab on load
+    #[serde(default, skip_serializing)]
+    widgets: Vec<Widget>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GridConfig {
+    #[serde(default = "default_columns")]
+    pub columns: usize,
+    /// Height of a grid row in terminal lines, borders included
+    #[serde(default = "default_row_height")]
+    pub row_height: u16,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        GridConfig {
+            columns: default_columns(),
+            row_height: default_row_height(),
+        }
+    }
+}
+
+fn default_columns() -> usize {
+    5
+}
+
+fn default_row_height() -> u16 {
+    3
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tab {
+    pub name: String,
+    #[serde(default)]
     pub widgets: Vec<Widget>,
 }
 
+impl Tab {
+    pub fn new(name: String) -> Self {
+        Tab {
+            name,
+            widgets: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Widget {
     pub topic: String,
     pub label: String,
     pub widget_type: WidgetType,
     pub position: GridPosition,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<GraphConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gauge: Option<GaugeConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boolean: Option<BooleanConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<FieldConfig>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GridPosition {
     pub row: usize,
     pub col: usize,
@@ -23,45 +82,230 @@ pub struct GridPosition {
     pub col_span: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl GridPosition {
+    /// Whether the cell at `row`, `col` is covered by this position's span
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row >= self.row
+            && row < self.row + self.row_span
+            && col >= self.col
+            && col < self.col + self.col_span
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum WidgetType {
     Text,
     Graph,
     Gauge,
+    Boolean,
+    /// SendableChooser table
+    Chooser,
+    /// Field2d table
+    Field,
+    /// Mechanism2d table
+    Mechanism,
+    /// Command table, shown as a button that schedules or cancels it
+    Command,
+    /// Subsystem table, showing its current and default commands
+    Subsystem,
+    /// PIDController table, with gains that can be tuned in place
+    Pid,
+    /// Alert group table
+    Alerts,
+}
+
+impl WidgetType {
+    pub fn next(self) -> Self {
+        match self {
+            WidgetType::Text => WidgetType::Graph,
+            WidgetType::Graph => WidgetType::Gauge,
+            WidgetType::Gauge => WidgetType::Boolean,
+            WidgetType::Boolean => WidgetType::Text,
+            // Sendable widgets only make sense for their own table
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphConfig {
+    /// How many seconds of history to show
+    #[serde(default = "default_graph_window")]
+    pub window_secs: f64,
+    /// Fixed y-axis bounds; the axis auto-scales when either is unset
+    pub y_min: Option<f64>,
+    pub y_max: Option<f64>,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        GraphConfig {
+            window_secs: default_graph_window(),
+            y_min: None,
+            y_max: None,
+            x_label: None,
+            y_label: None,
+        }
+    }
+}
+
+fn default_graph_window() -> f64 {
+    10.0
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GaugeConfig {
+    pub min: f64,
+    pub max: f64,
+    /// Value ranges (inclusive) that color the gauge; danger wins over warning
+    pub warning: Option<[f64; 2]>,
+    pub danger: Option<[f64; 2]>,
 }
 
+impl Default for GaugeConfig {
+    fn default() -> Self {
+        GaugeConfig {
+            min: 0.0,
+            max: 100.0,
+            warning: None,
+            danger: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BooleanConfig {
+    /// Any color ratatui can parse, e.g. "green" or "#00ff00"
+    #[serde(default = "default_true_color")]
+    pub true_color: String,
+    #[serde(default = "default_false_color")]
+    pub false_color: String,
+    pub true_label: Option<String>,
+    pub false_label: Option<String>,
+}
+
+impl Default for BooleanConfig {
+    fn default() -> Self {
+        BooleanConfig {
+            true_color: default_true_color(),
+            false_color: default_false_color(),
+            true_label: None,
+            false_label: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldConfig {
+    /// Field size in meters; the default is the 2025 FRC field
+    #[serde(default = "default_field_length")]
+    pub length: f64,
+    #[serde(default = "default_field_width")]
+    pub width: f64,
+    /// Side length of the robot outline in meters, bumpers included
+    #[serde(default = "default_robot_size")]
+    pub robot_size: f64,
+}
+
+impl Default for FieldConfig {
+    fn default() -> Self {
+        FieldConfig {
+            length: default_field_length(),
+            width: default_field_width(),
+            robot_size: default_robot_size(),
+        }
+    }
+}
+
+fn default_field_length() -> f64 {
+    17.548
+}
+
+fn default_field_width() -> f64 {
+    8.052
+}
+
+fn default_robot_size() -> f64 {
+    0.9
+}
+
+fn default_true_color() -> String {
+    "green".to_string()
+}
+
+fn default_false_color() -> String {
+    "red".to_string()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: PathBuf::new(),
+            grid: GridConfig::default(),
+            tabs: vec![Tab::new(DEFAULT_TAB_NAME.to_string())],
+            widgets: Vec::new(),
+        }
+    }
+}
+
+const DEFAULT_TAB_NAME: &str = "Main";
+
 impl Config {
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_path = get_config_path()?;
+    /// Loads the config from `path`, or from the user's config directory if none is given
+    pub fn load(path: Option<PathBuf>) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = match path {
+            Some(path) => path,
+            None => get_config_path()?,
+        };
 
         if !config_path.exists() {
             let default_config = Config {
-                widgets: Vec::new(),
+                path: config_path,
+                ..Default::default()
             };
             default_config.save()?;
             return Ok(default_config);
         }
 
-        let contents = fs::read_to_string(config_path)?;
-        let config: Config = toml::from_str(&contents)?;
+        let contents = fs::read_to_string(&config_path)?;
+        let mut config: Config = toml::from_str(&contents)?;
+        config.path = config_path;
+
+        // Migrate single-grid configs into a default tab
+        if !config.widgets.is_empty() {
+            let mut tab = Tab::new(DEFAULT_TAB_NAME.to_string());
+            tab.widgets = std::mem::take(&mut config.widgets);
+            config.tabs.insert(0, tab);
+            config.save()?;
+        }
+        if config.tabs.is_empty() {
+            config.tabs.push(Tab::new(DEFAULT_TAB_NAME.to_string()));
+        }
+
         Ok(config)
     }
 
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_path = get_config_path()?;
-
         // Ensure parent directories exist
-        if let Some(parent) = config_path.parent() {
+        if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         let toml_string = toml::to_string_pretty(self)?;
-        fs::write(config_path, toml_string)?;
+        fs::write(&self.path, toml_string)?;
         Ok(())
     }
 
-    pub fn add_widget(&mut self, widget: Widget) -> Result<(), Box<dyn std::error::Error>> {
-        self.widgets.push(widget);
+    pub fn add_widget(
+        &mut self,
+        tab: usize,
+        widget: Widget,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tab) = self.tabs.get_mut(tab) {
+            tab.widgets.push(widget);
+        }
         self.save()?;
         Ok(())
     }
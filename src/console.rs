@@ -0,0 +1,104 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+// How many log lines the in-app console keeps around
+const CONSOLE_CAPACITY: usize = 2000;
+
+static ENTRIES: Mutex<VecDeque<ConsoleEntry>> = Mutex::new(VecDeque::new());
+static START: OnceLock<Instant> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    /// Seconds since the logger was initialized
+    pub time: f64,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// Logger that writes to a file and keeps recent records for the console tab
+struct ConsoleLogger {
+    level: LevelFilter,
+    file: Option<Mutex<File>>,
+}
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let entry = ConsoleEntry {
+            time: START.get_or_init(Instant::now).elapsed().as_secs_f64(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+
+        if let Some(file) = &self.file
+            && let Ok(mut file) = file.lock()
+        {
+            let _ = writeln!(
+                file,
+                "[{:>10.3}] {:<5} [{}] {}",
+                entry.time, entry.level, entry.target, entry.message
+            );
+        }
+
+        if let Ok(mut entries) = ENTRIES.lock() {
+            if entries.len() >= CONSOLE_CAPACITY {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file
+            && let Ok(mut file) = file.lock()
+        {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Installs the global logger. Records are appended to `log_file` (if given) and
+/// kept in memory for the console tab.
+pub fn init(log_file: Option<&Path>, level: LevelFilter) -> Result<(), Box<dyn std::error::Error>> {
+    START.get_or_init(Instant::now);
+
+    let file = match log_file {
+        Some(path) => Some(Mutex::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        )),
+        None => None,
+    };
+
+    log::set_boxed_logger(Box::new(ConsoleLogger { level, file }))?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// Returns the buffered entries that pass `filter`, oldest first.
+/// Don't log from inside `filter`, the buffer is locked while it runs.
+pub fn entries(filter: impl Fn(&ConsoleEntry) -> bool) -> Vec<ConsoleEntry> {
+    match ENTRIES.lock() {
+        Ok(entries) => entries.iter().filter(|e| filter(e)).cloned().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn clear() {
+    if let Ok(mut entries) = ENTRIES.lock() {
+        entries.clear();
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::nt::NtUpdate;
+use crate::structs::StructRegistry;
+use crate::value::NtValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    /// One row per timestamp, one column per topic
+    Csv,
+    /// One JSON object per sample
+    Jsonl,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Format::Csv => Format::Jsonl,
+            Format::Jsonl => Format::Csv,
+        }
+    }
+}
+
+/// Timestamped values of each exported topic, in time order
+pub type Samples = BTreeMap<String, Vec<(f64, NtValue)>>;
+
+/// Which topics to export; an empty filter matches everything
+#[derive(Debug, Default)]
+pub struct TopicFilter {
+    pub topics: Vec<String>,
+    pub prefixes: Vec<String>,
+}
+
+impl TopicFilter {
+    pub fn matches(&self, topic: &str) -> bool {
+        (self.topics.is_empty() && self.prefixes.is_empty())
+            || self.topics.iter().any(|t| t == topic)
+            || self.prefixes.iter().any(|p| topic.starts_with(p.as_str()))
+    }
+}
+
+/// Collects matching values from a recording, keeping those between `from` and `to` seconds
+pub fn from_log(
+    updates: &[(f64, NtUpdate)],
+    filter: &TopicFilter,
+    from: Option<f64>,
+    to: Option<f64>,
+) -> Samples {
+    let mut samples = Samples::new();
+    let mut structs = StructRegistry::new();
+    for (time, update) in updates {
+        // Schemas are logged like any other topic, usually before the values that use them
+        if let NtUpdate::Updated(topic, value) = update {
+            structs.observe(topic, value);
+        }
+        if from.is_some_and(|from| *time < from) || to.is_some_and(|to| *time > to) {
+            continue;
+        }
+        if let NtUpdate::Updated(topic, value) = update
+            && filter.matches(topic)
+        {
+            samples
+                .entry(topic.clone())
+                .or_default()
+                .push((*time, structs.decode(value.clone())));
+        }
+    }
+    samples
+}
+
+pub fn write(out: &mut impl Write, samples: &Samples, format: Format) -> io::Result<()> {
+    match format {
+        Format::Csv => write_csv(out, samples),
+        Format::Jsonl => write_jsonl(out, samples),
+    }
+}
+
+/// Writes a row for every timestamp any topic changed at. Topics that didn't change
+/// repeat their previous value, and are empty until their first sample.
+fn write_csv(out: &mut impl Write, samples: &Samples) -> io::Result<()> {
+    write!(out, "time")?;
+    for topic in samples.keys() {
+        write!(out, ",{}", csv_field(topic))?;
+    }
+    writeln!(out)?;
+
+    let mut times: Vec<f64> = samples.values().flatten().map(|(t, _)| *t).collect();
+    times.sort_by(f64::total_cmp);
+    times.dedup();
+
+    // Index of the next sample to take from each topic
+    let mut cursors = vec![0; samples.len()];
+    let mut current: Vec<Option<&NtValue>> = vec![None; samples.len()];
+    for time in times {
+        for (i, values) in samples.values().enumerate() {
+            while let Some((t, value)) = values.get(cursors[i])
+                && *t <= time
+            {
+                current[i] = Some(value);
+                cursors[i] += 1;
+            }
+        }
+
+        write!(out, "{:.6}", time)?;
+        for value in &current {
+            match value {
+                Some(value) => write!(out, ",{}", csv_field(&value.to_string()))?,
+                None => write!(out, ",")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_jsonl(out: &mut impl Write, samples: &Samples) -> io::Result<()> {
+    let mut rows: Vec<(f64, &str, &NtValue)> = samples
+        .iter()
+        .flat_map(|(topic, values)| values.iter().map(move |(t, v)| (*t, topic.as_str(), v)))
+        .collect();
+    rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    for (time, topic, value) in rows {
+        writeln!(
+            out,
+            "{}",
+            json!({ "time": time, "topic": topic, "value": value.to_json() })
+        )?;
+    }
+    Ok(())
+}
+
+// Quotes fields that would otherwise break the row
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
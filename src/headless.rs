@@ -0,0 +1,325 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::time::Duration;
+
+use log::error;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{Receiver, Sender};
+use tokio::time::{Instant, timeout_at};
+
+use crate::cli::{Command, ExportArgs};
+use crate::export::{self, TopicFilter};
+use crate::nt::NtUpdate;
+use crate::playback;
+use crate::structs::StructRegistry;
+use crate::ui::ConnectionStatus;
+use crate::value::{NtType, NtValue};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_NOT_FOUND: i32 = 1;
+pub const EXIT_BAD_INPUT: i32 = 2;
+pub const EXIT_NO_SERVER: i32 = 3;
+pub const EXIT_INCOMPLETE: i32 = 4;
+
+/// How long announcements have to stop coming before the topic list counts as complete
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+pub struct Options {
+    pub json: bool,
+    pub timeout: Duration,
+    pub read_only: bool,
+}
+
+/// Runs a single command against the NT updates coming from `receiver` and returns the exit code
+pub async fn run(
+    command: Command,
+    mut receiver: Receiver<NtUpdate>,
+    sender: Sender<NtUpdate>,
+    options: Options,
+) -> i32 {
+    let deadline = Instant::now() + options.timeout;
+
+    let command = match command {
+        Command::Watch { prefix } => return watch(&prefix, receiver, &options).await,
+        Command::Export(args) => return export(args),
+        command => command,
+    };
+
+    let Some(mut snapshot) = Snapshot::collect(&mut receiver, deadline).await else {
+        eprintln!("Could not connect to the server");
+        return EXIT_NO_SERVER;
+    };
+    if snapshot.missed > 0 {
+        return missed_updates(snapshot.missed);
+    }
+
+    match command {
+        Command::List { prefix } => {
+            let prefix = prefix.unwrap_or_default();
+            for (topic, ty) in snapshot
+                .types
+                .iter()
+                .filter(|(t, _)| t.starts_with(&prefix))
+            {
+                if options.json {
+                    println!("{}", json!({ "topic": topic, "type": ty.as_str() }));
+                } else {
+                    println!("{}\t{}", topic, ty);
+                }
+            }
+            EXIT_OK
+        }
+        Command::Get { topic } => {
+            if !snapshot.types.contains_key(&topic) {
+                eprintln!("Topic {} is not announced", topic);
+                return EXIT_NOT_FOUND;
+            }
+            // Values can trail their announcement, so give it until the deadline
+            while !snapshot.values.contains_key(&topic) {
+                match timeout_at(deadline, receiver.recv()).await {
+                    Ok(Ok(update)) => snapshot.apply(update),
+                    // The value may have been one of the dropped updates
+                    Ok(Err(RecvError::Lagged(n))) => return missed_updates(n),
+                    _ => break,
+                }
+            }
+            let Some(value) = snapshot.values.get(&topic) else {
+                eprintln!("Topic {} has no value", topic);
+                return EXIT_NOT_FOUND;
+            };
+            // The schema may have come in after the value
+            let value = snapshot.structs.decode(value.clone());
+            print_value(&topic, &value, &options);
+            EXIT_OK
+        }
+        Command::Set { topic, value, ty } => {
+            if options.read_only {
+                eprintln!("Can't publish values in read-only mode");
+                return EXIT_BAD_INPUT;
+            }
+            let ty = match (snapshot.types.get(&topic), ty) {
+                (Some(announced), Some(ty)) if *announced != ty => {
+                    eprintln!("Topic {} is {}, not {}", topic, announced, ty);
+                    return EXIT_BAD_INPUT;
+                }
+                (Some(announced), _) => announced.clone(),
+                (None, Some(ty)) => ty,
+                (None, None) => {
+                    eprintln!(
+                        "Topic {} is not announced, pass --type to publish it anyway",
+                        topic
+                    );
+                    return EXIT_NOT_FOUND;
+                }
+            };
+            let value = match NtValue::parse(&ty, &value) {
+                Ok(value) => value,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return EXIT_BAD_INPUT;
+                }
+            };
+
+            if let Err(e) = sender.send(NtUpdate::Publish(topic.clone(), value.to_rmpv())) {
+                error!("Failed to queue publish for {}: {}", topic, e);
+                return EXIT_NO_SERVER;
+            }
+            // Wait for the publisher to report back before claiming success
+            let deadline = Instant::now() + options.timeout;
+            loop {
+                match timeout_at(deadline, receiver.recv()).await {
+                    Ok(Ok(NtUpdate::PublishResult(published, result))) if published == topic => {
+                        if let Err(e) = result {
+                            eprintln!("Failed to publish {}: {}", topic, e);
+                            return EXIT_NO_SERVER;
+                        }
+                        break;
+                    }
+                    Ok(Ok(_)) => {}
+                    Ok(Err(RecvError::Lagged(n))) => return missed_updates(n),
+                    Ok(Err(RecvError::Closed)) | Err(_) => {
+                        eprintln!("Timed out publishing {}", topic);
+                        return EXIT_NO_SERVER;
+                    }
+                }
+            }
+            print_value(&topic, &value, &options);
+            EXIT_OK
+        }
+        Command::Watch { .. } | Command::Export(_) => unreachable!(),
+    }
+}
+
+async fn watch(prefix: &str, mut receiver: Receiver<NtUpdate>, options: &Options) -> i32 {
+    let start = Instant::now();
+    let deadline = start + options.timeout;
+    let mut connected = false;
+    let mut ever_connected = false;
+    let mut structs = StructRegistry::new();
+    loop {
+        // Only the first connection is bounded by the timeout; after that, wait out reconnects
+        let update = if ever_connected {
+            receiver.recv().await
+        } else {
+            match timeout_at(deadline, receiver.recv()).await {
+                Ok(update) => update,
+                Err(_) => {
+                    eprintln!("Could not connect to the server");
+                    return EXIT_NO_SERVER;
+                }
+            }
+        };
+        match update {
+            Ok(NtUpdate::Updated(topic, value)) => {
+                structs.observe(&topic, &value);
+                if !topic.starts_with(prefix) {
+                    continue;
+                }
+                let value = structs.decode(value);
+                let time = start.elapsed().as_secs_f64();
+                if options.json {
+                    println!(
+                        "{}",
+                        json!({
+                            "time": time,
+                            "topic": topic,
+                            "type": value.nt_type().as_str(),
+                            "value": value.to_json(),
+                        })
+                    );
+                } else {
+                    println!("[{:>10.3}] {} = {}", time, topic, value);
+                }
+            }
+            Ok(NtUpdate::ConnectionStatus(status)) => match status {
+                ConnectionStatus::Connected => {
+                    connected = true;
+                    ever_connected = true;
+                }
+                ConnectionStatus::Disconnected if connected => {
+                    connected = false;
+                    eprintln!("Disconnected from the server, reconnecting...");
+                }
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(RecvError::Lagged(n)) => eprintln!("Fell behind, skipped {} updates", n),
+            Err(RecvError::Closed) => return EXIT_NO_SERVER,
+        }
+    }
+}
+
+fn export(args: ExportArgs) -> i32 {
+    let updates = match playback::read_log(&args.log) {
+        Ok(updates) => updates,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", args.log.display(), e);
+            return EXIT_BAD_INPUT;
+        }
+    };
+    let filter = TopicFilter {
+        topics: args.topics,
+        prefixes: args.prefixes,
+    };
+    let samples = export::from_log(&updates, &filter, args.from, args.to);
+    if samples.is_empty() {
+        eprintln!("No values in {} matched", args.log.display());
+        return EXIT_NOT_FOUND;
+    }
+
+    let result = match &args.output {
+        Some(path) => File::create(path)
+            .map(BufWriter::new)
+            .and_then(|mut out| export::write(&mut out, &samples, args.format)),
+        None => export::write(&mut io::stdout().lock(), &samples, args.format),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to write export: {}", e);
+        return EXIT_BAD_INPUT;
+    }
+    EXIT_OK
+}
+
+/// Topics and values the server sent right after connecting
+#[derive(Default)]
+struct Snapshot {
+    types: BTreeMap<String, NtType>,
+    values: HashMap<String, NtValue>,
+    structs: StructRegistry,
+    /// Updates dropped because the channel overflowed while collecting
+    missed: u64,
+}
+
+impl Snapshot {
+    /// Waits for a connection, then collects updates until announcements settle.
+    /// Returns `None` if the server couldn't be reached before `deadline`.
+    async fn collect(receiver: &mut Receiver<NtUpdate>, deadline: Instant) -> Option<Self> {
+        let mut snapshot = Snapshot::default();
+        let mut connected = false;
+        let mut settle = deadline;
+        loop {
+            let wait_until = if connected {
+                settle.min(deadline)
+            } else {
+                deadline
+            };
+            match timeout_at(wait_until, receiver.recv()).await {
+                Ok(Ok(NtUpdate::ConnectionStatus(ConnectionStatus::Connected))) => {
+                    connected = true;
+                    settle = Instant::now() + SETTLE_TIME;
+                }
+                Ok(Ok(update)) => {
+                    if matches!(update, NtUpdate::Announced(..)) {
+                        settle = Instant::now() + SETTLE_TIME;
+                    }
+                    snapshot.apply(update);
+                }
+                Ok(Err(RecvError::Lagged(n))) => snapshot.missed += n,
+                Ok(Err(RecvError::Closed)) => return None,
+                Err(_) => return connected.then_some(snapshot),
+            }
+        }
+    }
+
+    fn apply(&mut self, update: NtUpdate) {
+        match update {
+            NtUpdate::Announced(topic, ty) => {
+                self.types.insert(topic, ty);
+            }
+            NtUpdate::Updated(topic, value) => {
+                self.structs.observe(&topic, &value);
+                self.values.insert(topic, value);
+            }
+            NtUpdate::Unannounced(topic) => {
+                self.types.remove(&topic);
+                self.values.remove(&topic);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn missed_updates(count: u64) -> i32 {
+    eprintln!(
+        "Fell behind the server and missed {} updates, the output would be incomplete",
+        count
+    );
+    EXIT_INCOMPLETE
+}
+
+fn print_value(topic: &str, value: &NtValue, options: &Options) {
+    if options.json {
+        println!(
+            "{}",
+            json!({
+                "topic": topic,
+                "type": value.nt_type().as_str(),
+                "value": value.to_json(),
+            })
+        );
+    } else {
+        println!("{}", value);
+    }
+}
@@ -1,78 +1,132 @@
+mod cli;
 mod config;
+mod console;
+mod export;
+mod headless;
 mod nt;
+mod playback;
+mod protobuf;
+mod recorder;
+mod structs;
 mod ui;
+mod value;
 
+use crate::cli::Cli;
+use crate::config::Config;
 use crate::ui::ConnectionStatus;
-use log::{LevelFilter, error, info};
-use nt_client::{NTAddr, NewClientOptions, error::ReconnectError};
-use std::str::FromStr;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use log::{error, info};
+use nt_client::{NewClientOptions, error::ReconnectError};
+use std::thread;
 use std::time::Duration;
-use std::{net::Ipv4Addr, thread};
 use tokio::sync::broadcast::{Sender, channel};
+use tokio::sync::mpsc::unbounded_channel;
 
 #[tokio::main]
 async fn main() {
-    let arg = std::env::args().nth(1);
-    match arg {
-        Some(arg) => match arg.as_str() {
-            "--address" => {}
-            _ => {
-                println!("Invalid argument: {}. Valid arguments: --address", arg);
-                std::process::exit(1);
+    let mut cli = Cli::parse();
+
+    if let Err(e) = console::init(cli.log_file.as_deref(), cli.log_level.into()) {
+        eprintln!("Failed to set up logging: {}", e);
+        std::process::exit(1);
+    }
+
+    // Create channel for NT updates. Headless commands take in the server's whole initial
+    // burst without a UI to pace them, and playback sends up to a few hundred a tick,
+    // so give those more room. The dashboard needs enough for a recording to take in
+    // the initial burst too.
+    let capacity = if cli.command.is_some() {
+        16384
+    } else if cli.replay.is_some() {
+        4096
+    } else {
+        1024
+    };
+    let (sender, receiver) = channel(capacity);
+    let client_opts = cli.client_options();
+
+    if let Some(command) = cli.command.take() {
+        if cli.replay.is_some() {
+            eprintln!("--replay only works with the dashboard, not with subcommands");
+            std::process::exit(headless::EXIT_BAD_INPUT);
+        }
+        if command.needs_server() {
+            if cli.address.is_none() && cli.team.is_none() {
+                Cli::command()
+                    .error(
+                        ErrorKind::MissingRequiredArgument,
+                        "this subcommand needs a server, pass --address or --team",
+                    )
+                    .exit();
             }
-        },
-        None => {
-            println!("No argument given. Valid arguments: --address");
-            std::process::exit(1);
+            tokio::spawn(run_nt_with_reconnect(
+                sender.clone(),
+                client_opts,
+                cli.read_only,
+            ));
         }
-    }
-    let addr_arg = std::env::args().nth(2);
-    if addr_arg.is_none() {
-        println!("No arguments passed to --address");
-        std::process::exit(1);
+        let options = headless::Options {
+            json: cli.json,
+            timeout: Duration::from_secs(cli.timeout),
+            read_only: cli.read_only,
+        };
+        std::process::exit(headless::run(command, receiver, sender, options).await);
     }
 
-    let addr_arg = addr_arg.unwrap();
-    let addr = match addr_arg.parse::<u16>() {
-        Ok(n) => NTAddr::TeamNumber(n),
-        Err(_) => {
-            let a = Ipv4Addr::from_str(addr_arg.as_str());
-            match a {
-                Ok(ip) => NTAddr::Custom(ip),
-                Err(e) => {
-                    if addr_arg == "localhost" {
-                        NTAddr::Local
-                    } else {
-                        eprintln!("Invalid address: {}", e);
-                        std::process::exit(1);
-                    }
-                }
+    let config = match Config::load(cli.config.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            match &cli.config {
+                Some(path) => eprintln!("Failed to load config {}: {}", path.display(), e),
+                None => eprintln!("Failed to load config: {}", e),
             }
+            std::process::exit(1);
         }
     };
-    let _ = simple_logging::log_to_file("test.log", LevelFilter::Debug);
 
-    // Create channel for NT updates
-    let (sender, receiver) = channel(128);
-
-    let client_opts = NewClientOptions {
-        addr, // Can be changed to custom address if needed
-        ..Default::default()
+    let mut playback_control = None;
+    let nt_task = match &cli.replay {
+        Some(path) => {
+            let updates = match playback::read_log(path) {
+                Ok(updates) => updates,
+                Err(e) => {
+                    eprintln!("Failed to read {}: {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            };
+            let (control_sender, controls) = unbounded_channel();
+            playback_control = Some(control_sender);
+            tokio::spawn(playback::run_playback(sender.clone(), controls, updates))
+        }
+        // Start NT client with reconnection handling in a separate task
+        None => tokio::spawn(run_nt_with_reconnect(
+            sender.clone(),
+            client_opts.clone(),
+            cli.read_only,
+        )),
     };
 
-    // Start NT client with reconnection handling in a separate task
-
-    let nt_task = tokio::spawn(run_nt_with_reconnect(sender.clone(), client_opts.clone()));
-
+    let options = ui::Options {
+        // There's nothing to publish to when replaying
+        read_only: cli.read_only || cli.replay.is_some(),
+        record: cli.record,
+        record_dir: cli.record_dir,
+        playback_control,
+    };
     // Run the UI with the receiver (this blocks the main thread)
-    ui::run_ui(receiver).unwrap();
+    ui::run_ui(receiver, sender, config, options).unwrap();
     // thread::sleep(Duration::from_secs(100));
 
     // When UI exits, abort all tasks
     nt_task.abort();
 }
 
-async fn run_nt_with_reconnect(sender: Sender<nt::NtUpdate>, client_opts: NewClientOptions) {
+async fn run_nt_with_reconnect(
+    sender: Sender<nt::NtUpdate>,
+    client_opts: NewClientOptions,
+    read_only: bool,
+) {
     // Run reconnect handler
     nt_client::reconnect(client_opts, |client| {
         // Create a new sender for this reconnection attempt
@@ -88,9 +142,11 @@ async fn run_nt_with_reconnect(sender: Sender<nt::NtUpdate>, client_opts: NewCli
             tokio::spawn(nt::run_nt_client(sender_c.clone(), topics));
             tokio::spawn(nt::run_nt_client_topics(sender_c.clone(), topics_c));
 
-            let recv = sender_c.clone().subscribe();
-            let generic_publisher = client.generic_publisher();
-            tokio::spawn(nt::run_nt_publisher(recv, generic_publisher));
+            if !read_only {
+                let recv = sender_c.clone().subscribe();
+                let generic_publisher = client.generic_publisher();
+                tokio::spawn(nt::run_nt_publisher(recv, sender_c.clone(), generic_publisher));
+            }
 
             tokio::select! {
                 conn_result = client.connect() => {
@@ -1,10 +1,14 @@
+use crate::playback::PlaybackStatus;
 use crate::ui::ConnectionStatus;
+use crate::value::{NtType, NtValue};
 use log::error;
 use log::info;
 use log::warn;
 use nt_client::data::SubscriptionOptions;
+use nt_client::data::r#type::DataType;
 use nt_client::publish::GenericPublisher;
 use nt_client::subscribe::ReceivedMessage;
+use nt_client::topic::AnnouncedTopic;
 use nt_client::topic::Topic;
 use rmpv::Value;
 use tokio::sync::broadcast::Receiver;
@@ -12,9 +16,17 @@ use tokio::sync::broadcast::Sender;
 #[derive(Debug, Clone)]
 
 pub enum NtUpdate {
-    Subscribed(String, String),
+    Announced(String, NtType),
+    Updated(String, NtValue),
+    Unannounced(String),
     Publish(String, Value),
+    /// Whether a `Publish` made it out, sent back by the publisher
+    PublishResult(String, Result<(), String>),
     ConnectionStatus(ConnectionStatus),
+    /// Playback clock and transport state while replaying a log
+    Playback(PlaybackStatus),
+    /// Forget all topics; sent by playback before it rebuilds the state after a seek
+    Reset,
 }
 
 pub async fn run_nt_client(sender: Sender<NtUpdate>, topics: Topic) {
@@ -27,6 +39,7 @@ pub async fn run_nt_client(sender: Sender<NtUpdate>, topics: Topic) {
         .await;
 
     // If we're subscribing successfully, mark as connected
+    info!("Connected to NT server");
     let _ = sender.send(NtUpdate::ConnectionStatus(ConnectionStatus::Connected));
 
     // Process messages from all topics in the collection
@@ -35,14 +48,10 @@ pub async fn run_nt_client(sender: Sender<NtUpdate>, topics: Topic) {
             Ok(ReceivedMessage::Announced(topic)) => {
                 let topic_name = topic.name().to_string();
                 info!("Announced topic: {}", topic_name);
-                let _ = sender.send(NtUpdate::Subscribed(
-                    topic.name().to_string(),
-                    "None".to_owned(),
-                ));
+                let _ = sender.send(NtUpdate::Announced(topic_name, topic_type(&topic)));
             }
             Ok(ReceivedMessage::Updated((topic, value))) => {
-                let value = value.to_string().trim().to_string();
-                let _ = sender.send(NtUpdate::Subscribed(topic.name().to_string(), value));
+                let _ = sender.send(decode_update(topic.name(), &topic_type(&topic), value));
             }
             Err(err) => {
                 warn!("Warning on specific watcher thread: {err:?}");
@@ -66,13 +75,11 @@ pub async fn run_nt_client_topics(sender: Sender<NtUpdate>, topics: Topic) {
             Ok(ReceivedMessage::Announced(topic)) => {
                 let topic_name = topic.name().to_string();
                 info!("Announced topic: {}", topic_name);
-                let _ = sender.send(NtUpdate::Subscribed(
-                    topic.name().to_string(),
-                    "None".to_owned(),
-                ));
+                let _ = sender.send(NtUpdate::Announced(topic_name, topic_type(&topic)));
             }
             Ok(ReceivedMessage::Unannounced { name, .. }) => {
                 info!("Unannounced topic: {}", name);
+                let _ = sender.send(NtUpdate::Unannounced(name));
             }
             Err(err) => {
                 warn!("Warning on topics thread: {err:?}");
@@ -84,16 +91,18 @@ pub async fn run_nt_client_topics(sender: Sender<NtUpdate>, topics: Topic) {
 
 pub async fn run_nt_publisher(
     mut receiver: Receiver<NtUpdate>,
+    sender: Sender<NtUpdate>,
     generic_publisher: GenericPublisher,
 ) {
     loop {
         match receiver.recv().await {
             Ok(msg) => if let NtUpdate::Publish(k, v) = msg {
                 let r = generic_publisher.set(k.clone(), v).await;
-                match r {
+                match &r {
                     Ok(_) => info!("Set key: {}", k),
                     Err(err) => warn!("Error setting key: {}", err),
                 }
+                let _ = sender.send(NtUpdate::PublishResult(k, r.map_err(|e| e.to_string())));
             },
             Err(e) => {
                 error!("error in publish: {e}")
@@ -101,3 +110,99 @@ pub async fn run_nt_publisher(
         }
     }
 }
+
+/// The topic's type as nt_client reports it. Its `DataType` has no room for `struct:` or
+/// `proto:` type names, so those topics come through as raw bytes here. They're still
+/// decoded from .wpilog files that kept the full type, like the robot's own logs.
+pub fn topic_type(topic: &AnnouncedTopic) -> NtType {
+    match topic.r#type() {
+        DataType::Boolean => NtType::Boolean,
+        DataType::Double => NtType::Double,
+        DataType::Int => NtType::Int,
+        DataType::Float => NtType::Float,
+        DataType::String => NtType::String,
+        DataType::Json => NtType::Json,
+        DataType::Raw => NtType::Raw,
+        DataType::BooleanArray => NtType::BooleanArray,
+        DataType::DoubleArray => NtType::DoubleArray,
+        DataType::IntArray => NtType::IntArray,
+        DataType::FloatArray => NtType::FloatArray,
+        DataType::StringArray => NtType::StringArray,
+        DataType::Rpc => NtType::Other("rpc".to_string()),
+        DataType::Msgpack => NtType::Other("msgpack".to_string()),
+        DataType::Protobuf => NtType::Other("protobuf".to_string()),
+    }
+}
+
+/// Builds the update for a value received on `name`, decoding it by its announced type
+pub fn decode_update(name: &str, ty: &NtType, value: Value) -> NtUpdate {
+    NtUpdate::Updated(name.to_string(), NtValue::decode(ty, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::StructRegistry;
+
+    #[test]
+    fn struct_values_keep_their_announced_type() {
+        let ty = NtType::from_type_str("struct:Pose2d");
+        let bytes = vec![0u8; 24];
+        match decode_update("/pose", &ty, Value::Binary(bytes.clone())) {
+            NtUpdate::Updated(name, value) => {
+                assert_eq!(name, "/pose");
+                assert_eq!(
+                    value,
+                    NtValue::Other(
+                        NtType::Other("struct:Pose2d".to_string()),
+                        Value::Binary(bytes)
+                    )
+                );
+            }
+            other => panic!("unexpected update {:?}", other),
+        }
+    }
+
+    #[test]
+    fn struct_updates_decode_once_their_schema_arrives() {
+        let mut registry = StructRegistry::new();
+        let updates = [
+            (
+                "/.schema/struct:Translation2d",
+                "structschema",
+                b"double x;double y".to_vec(),
+            ),
+            (
+                "/SmartDashboard/Field/Robot",
+                "struct:Translation2d",
+                [1.5f64.to_le_bytes(), (-2.0f64).to_le_bytes()].concat(),
+            ),
+        ];
+        // Same steps the UI takes for each update
+        let mut last = None;
+        for (name, ty, bytes) in updates {
+            let NtUpdate::Updated(name, value) =
+                decode_update(name, &NtType::from_type_str(ty), Value::Binary(bytes))
+            else {
+                panic!("expected an update");
+            };
+            registry.observe(&name, &value);
+            last = Some(registry.decode(value));
+        }
+
+        let Some(NtValue::Struct(ty, _, decoded)) = last else {
+            panic!("the value wasn't decoded: {:?}", last);
+        };
+        assert_eq!(ty, NtType::Other("struct:Translation2d".to_string()));
+        assert_eq!(decoded.to_json(), serde_json::json!({"x": 1.5, "y": -2.0}));
+    }
+
+    #[test]
+    fn standard_types_still_decode() {
+        let ty = NtType::from_type_str("double");
+        match decode_update("/speed", &ty, Value::from(1.5)) {
+            NtUpdate::Updated(_, value) => assert_eq!(value, NtValue::Double(1.5)),
+            other => panic!("unexpected update {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use log::{info, warn};
+use rmpv::Value;
+use tokio::sync::broadcast::Sender;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::error::TryRecvError;
+
+use crate::nt::NtUpdate;
+use crate::recorder::{CONTROL_FINISH, CONTROL_START};
+use crate::ui::ConnectionStatus;
+use crate::value::{NtType, NtValue};
+
+const TICK: Duration = Duration::from_millis(20);
+// Cap on updates sent per tick, counting the state rebuilt after a seek, so a fast
+// replay or a seek in a busy log doesn't overrun the UI's end of the channel
+const MAX_UPDATES_PER_TICK: usize = 256;
+
+/// Transport controls sent from the UI to the player
+#[derive(Debug, Clone, Copy)]
+pub enum PlaybackControl {
+    TogglePlay,
+    /// Jump by this many seconds, backwards if negative
+    Seek(f64),
+    /// Jump to the next logged update and pause there
+    Step,
+    /// Multiply the playback speed by this factor
+    Speed(f64),
+}
+
+/// Where the player is, sent to the UI every tick
+#[derive(Debug, Clone, Copy)]
+pub struct PlaybackStatus {
+    pub position: f64,
+    pub duration: f64,
+    pub playing: bool,
+    pub speed: f64,
+}
+
+/// Reads a WPILib DataLog into updates, timed in seconds from the first record
+pub fn read_log(path: &Path) -> Result<Vec<(f64, NtUpdate)>, Box<dyn std::error::Error>> {
+    let data = fs::read(path)?;
+    let mut reader = ByteReader::new(&data);
+
+    if reader.bytes(6) != Some(b"WPILOG") {
+        return Err("not a .wpilog file".into());
+    }
+    let version = reader.uint(2).ok_or("truncated header")?;
+    if version >> 8 != 1 {
+        return Err(format!(
+            "unsupported .wpilog version {}.{}",
+            version >> 8,
+            version & 0xff
+        )
+        .into());
+    }
+    let extra_header_len = reader.uint(4).ok_or("truncated header")? as usize;
+    reader.bytes(extra_header_len).ok_or("truncated header")?;
+
+    let mut entries: HashMap<u32, (String, NtType)> = HashMap::new();
+    let mut updates = Vec::new();
+    let mut first_timestamp = None;
+    while !reader.is_empty() {
+        let Some((id, timestamp, payload)) = reader.record() else {
+            // Logs from a robot that lost power just stop, so keep what we have
+            warn!("{} ends with a partial record", path.display());
+            break;
+        };
+        let first = *first_timestamp.get_or_insert(timestamp);
+        let time = (timestamp as i64 - first as i64) as f64 / 1e6;
+
+        if id != 0 {
+            if let Some((name, ty)) = entries.get(&id) {
+                updates.push((
+                    time,
+                    NtUpdate::Updated(name.clone(), decode_value(ty, payload)),
+                ));
+            }
+            continue;
+        }
+
+        let mut control = ByteReader::new(payload);
+        match control.uint(1).map(|t| t as u8) {
+            Some(CONTROL_START) => {
+                let (Some(id), Some(name), Some(ty)) =
+                    (control.uint(4), control.string(), control.string())
+                else {
+                    continue;
+                };
+                let ty = match ty.as_str() {
+                    "int64" => NtType::Int,
+                    "int64[]" => NtType::IntArray,
+                    other => NtType::from_type_str(other),
+                };
+                entries.insert(id as u32, (name.clone(), ty.clone()));
+                updates.push((time, NtUpdate::Announced(name, ty)));
+            }
+            Some(CONTROL_FINISH) => {
+                let finished = control.uint(4).and_then(|id| entries.remove(&(id as u32)));
+                if let Some((name, _)) = finished {
+                    updates.push((time, NtUpdate::Unannounced(name)));
+                }
+            }
+            // Metadata changes don't affect what we show
+            _ => {}
+        }
+    }
+
+    // Records are usually in order, but the format doesn't promise it
+    updates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Ok(updates)
+}
+
+/// Feeds `updates` into the channel in step with a playback clock, taking controls from `controls`
+pub async fn run_playback(
+    sender: Sender<NtUpdate>,
+    mut controls: UnboundedReceiver<PlaybackControl>,
+    updates: Vec<(f64, NtUpdate)>,
+) {
+    let mut status = PlaybackStatus {
+        position: 0.0,
+        duration: updates.last().map(|(t, _)| *t).unwrap_or(0.0),
+        playing: true,
+        speed: 1.0,
+    };
+    let mut next = 0;
+    // State rebuilt by a seek, still to be sent before the log carries on
+    let mut rebuilt = VecDeque::new();
+
+    info!(
+        "Replaying {} updates over {:.1}s",
+        updates.len(),
+        status.duration
+    );
+    let _ = sender.send(NtUpdate::ConnectionStatus(ConnectionStatus::Connected));
+
+    let mut interval = tokio::time::interval(TICK);
+    loop {
+        interval.tick().await;
+
+        loop {
+            let control = match controls.try_recv() {
+                Ok(control) => control,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            };
+            match control {
+                PlaybackControl::TogglePlay => {
+                    // Playing again from the end starts over
+                    if !status.playing && status.position >= status.duration {
+                        (next, rebuilt) = seek(&updates, 0.0);
+                        status.position = 0.0;
+                    }
+                    status.playing = !status.playing;
+                }
+                PlaybackControl::Seek(delta) => {
+                    status.position = (status.position + delta).clamp(0.0, status.duration);
+                    (next, rebuilt) = seek(&updates, status.position);
+                }
+                PlaybackControl::Step => {
+                    status.playing = false;
+                    if let Some((time, _)) = updates.get(next) {
+                        status.position = *time;
+                    }
+                }
+                PlaybackControl::Speed(factor) => {
+                    status.speed = (status.speed * factor).clamp(0.125, 64.0);
+                }
+            }
+        }
+
+        if status.playing {
+            status.position += TICK.as_secs_f64() * status.speed;
+            if status.position >= status.duration {
+                status.position = status.duration;
+                status.playing = false;
+            }
+        }
+
+        // The clock goes out first so the UI timestamps this tick's updates correctly
+        let _ = sender.send(NtUpdate::Playback(status));
+        let mut sent = 0;
+        while sent < MAX_UPDATES_PER_TICK
+            && let Some(update) = rebuilt.pop_front()
+        {
+            let _ = sender.send(update);
+            sent += 1;
+        }
+        // The log only carries on once the UI has the whole rebuilt state
+        while rebuilt.is_empty()
+            && sent < MAX_UPDATES_PER_TICK
+            && let Some((time, update)) = updates.get(next)
+            && *time <= status.position
+        {
+            let _ = sender.send(update.clone());
+            next += 1;
+            sent += 1;
+        }
+    }
+}
+
+/// Returns the index of the next update to send after a seek to `position`, and the updates
+/// that rebuild the UI's state there
+fn seek(updates: &[(f64, NtUpdate)], position: f64) -> (usize, VecDeque<NtUpdate>) {
+    let next = updates.partition_point(|(time, _)| *time <= position);
+
+    // Only the last value of each topic that's still announced matters
+    let mut topics: BTreeMap<&str, (&NtType, Option<&NtValue>)> = BTreeMap::new();
+    for (_, update) in &updates[..next] {
+        match update {
+            NtUpdate::Announced(name, ty) => {
+                topics.insert(name, (ty, None));
+            }
+            NtUpdate::Updated(name, value) => {
+                if let Some((_, last)) = topics.get_mut(name.as_str()) {
+                    *last = Some(value);
+                }
+            }
+            NtUpdate::Unannounced(name) => {
+                topics.remove(name.as_str());
+            }
+            _ => {}
+        }
+    }
+
+    let mut rebuilt = VecDeque::from([NtUpdate::Reset]);
+    for (name, (ty, value)) in topics {
+        rebuilt.push_back(NtUpdate::Announced(name.to_string(), ty.clone()));
+        if let Some(value) = value {
+            rebuilt.push_back(NtUpdate::Updated(name.to_string(), value.clone()));
+        }
+    }
+    (next, rebuilt)
+}
+
+fn decode_value(ty: &NtType, payload: &[u8]) -> NtValue {
+    try_decode_value(ty, payload)
+        .unwrap_or_else(|| NtValue::Other(ty.clone(), Value::Binary(payload.to_vec())))
+}
+
+fn try_decode_value(ty: &NtType, payload: &[u8]) -> Option<NtValue> {
+    Some(match ty {
+        NtType::Boolean => NtValue::Boolean(*payload.first()? != 0),
+        NtType::Int => NtValue::Int(i64::from_le_bytes(payload.try_into().ok()?)),
+        NtType::Float => NtValue::Float(f32::from_le_bytes(payload.try_into().ok()?)),
+        NtType::Double => NtValue::Double(f64::from_le_bytes(payload.try_into().ok()?)),
+        NtType::String => NtValue::String(String::from_utf8_lossy(payload).into_owned()),
+        NtType::Json => NtValue::Json(String::from_utf8_lossy(payload).into_owned()),
+        NtType::Raw => NtValue::Raw(payload.to_vec()),
+        NtType::BooleanArray => NtValue::BooleanArray(payload.iter().map(|b| *b != 0).collect()),
+        NtType::IntArray => NtValue::IntArray(
+            payload
+                .chunks_exact(8)
+                .map(|c| i64::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        NtType::FloatArray => NtValue::FloatArray(
+            payload
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        NtType::DoubleArray => NtValue::DoubleArray(
+            payload
+                .chunks_exact(8)
+                .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+                .collect(),
+        ),
+        NtType::StringArray => {
+            let mut reader = ByteReader::new(payload);
+            let count = reader.uint(4)?;
+            NtValue::StringArray((0..count).map(|_| reader.string()).collect::<Option<_>>()?)
+        }
+        // Our own recordings store values that aren't plain bytes as msgpack
+        NtType::Other(ty) if ty == "msgpack" => NtValue::Other(
+            NtType::Other(ty.clone()),
+            rmpv::decode::read_value(&mut &payload[..]).ok()?,
+        ),
+        NtType::Other(_) => return None,
+    })
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        ByteReader { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    /// Little-endian unsigned integer of `len` bytes
+    fn uint(&mut self, len: usize) -> Option<u64> {
+        let bytes = self.bytes(len)?;
+        Some(
+            bytes
+                .iter()
+                .rev()
+                .fold(0u64, |acc, b| (acc << 8) | *b as u64),
+        )
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.uint(4)? as usize;
+        Some(String::from_utf8_lossy(self.bytes(len)?).into_owned())
+    }
+
+    /// Entry id, timestamp and payload of the next record
+    fn record(&mut self) -> Option<(u32, u64, &'a [u8])> {
+        let header = self.uint(1)? as usize;
+        let id = self.uint((header & 0x3) + 1)? as u32;
+        let size = self.uint(((header >> 2) & 0x3) + 1)? as usize;
+        let timestamp = self.uint(((header >> 4) & 0x7) + 1)?;
+        Some((id, timestamp, self.bytes(size)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> Vec<(f64, NtUpdate)> {
+        vec![
+            (0.0, NtUpdate::Announced("/a".to_string(), NtType::Double)),
+            (0.0, NtUpdate::Announced("/b".to_string(), NtType::String)),
+            (
+                1.0,
+                NtUpdate::Updated("/a".to_string(), NtValue::Double(1.0)),
+            ),
+            (
+                2.0,
+                NtUpdate::Updated("/a".to_string(), NtValue::Double(2.0)),
+            ),
+            (2.0, NtUpdate::Unannounced("/b".to_string())),
+            (
+                3.0,
+                NtUpdate::Updated("/a".to_string(), NtValue::Double(3.0)),
+            ),
+        ]
+    }
+
+    #[test]
+    fn seek_rebuilds_the_latest_state() {
+        let updates = log();
+        let (next, rebuilt) = seek(&updates, 2.5);
+        assert_eq!(next, 5);
+        let rebuilt: Vec<String> = rebuilt.iter().map(|u| format!("{:?}", u)).collect();
+        assert_eq!(
+            rebuilt,
+            [
+                NtUpdate::Reset,
+                NtUpdate::Announced("/a".to_string(), NtType::Double),
+                NtUpdate::Updated("/a".to_string(), NtValue::Double(2.0)),
+            ]
+            .iter()
+            .map(|u| format!("{:?}", u))
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seek_to_the_start_keeps_announcements_without_values() {
+        let updates = log();
+        let (next, rebuilt) = seek(&updates, 0.0);
+        assert_eq!(next, 2);
+        assert_eq!(rebuilt.len(), 3);
+        assert!(matches!(rebuilt[0], NtUpdate::Reset));
+    }
+}
@@ -0,0 +1,564 @@
+use std::collections::HashMap;
+
+use crate::structs::StructValue;
+
+// FieldDescriptorProto.Type values
+const TYPE_DOUBLE: u64 = 1;
+const TYPE_FLOAT: u64 = 2;
+const TYPE_INT64: u64 = 3;
+const TYPE_UINT64: u64 = 4;
+const TYPE_INT32: u64 = 5;
+const TYPE_FIXED64: u64 = 6;
+const TYPE_FIXED32: u64 = 7;
+const TYPE_BOOL: u64 = 8;
+const TYPE_STRING: u64 = 9;
+const TYPE_MESSAGE: u64 = 11;
+const TYPE_BYTES: u64 = 12;
+const TYPE_UINT32: u64 = 13;
+const TYPE_ENUM: u64 = 14;
+const TYPE_SFIXED32: u64 = 15;
+const TYPE_SFIXED64: u64 = 16;
+const TYPE_SINT32: u64 = 17;
+const TYPE_SINT64: u64 = 18;
+
+const LABEL_REPEATED: u64 = 3;
+
+// Messages nested deeper than this are assumed to be a bad or recursive schema
+const MAX_DEPTH: usize = 32;
+
+/// Message descriptors built from the FileDescriptorProtos under `/.schema/proto:*`,
+/// used to decode `proto:` topics without generated code
+#[derive(Default)]
+pub struct ProtoRegistry {
+    /// Fields of each message, by fully qualified name (e.g. `wpi.proto.ProtobufPose2d`)
+    messages: HashMap<String, Vec<Field>>,
+    enums: HashMap<String, Vec<(String, i64)>>,
+}
+
+struct Field {
+    name: String,
+    number: u64,
+    kind: u64,
+    repeated: bool,
+    /// Fully qualified message or enum name, for message and enum fields
+    type_name: String,
+}
+
+#[derive(Clone, Copy)]
+enum Wire<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl ProtoRegistry {
+    /// Adds the messages and enums in a serialized FileDescriptorProto.
+    /// Returns true if it defined anything new.
+    pub fn add_file(&mut self, data: &[u8]) -> Result<bool, String> {
+        let fields = parse_wire(data).ok_or("malformed FileDescriptorProto")?;
+        let package = fields
+            .iter()
+            .find_map(|(number, wire)| match (number, wire) {
+                (2, Wire::Bytes(b)) => Some(String::from_utf8_lossy(b).into_owned()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let known = self.messages.len() + self.enums.len();
+        for (number, wire) in fields {
+            match (number, wire) {
+                (4, Wire::Bytes(b)) => self.add_message(&package, b)?,
+                (5, Wire::Bytes(b)) => self.add_enum(&package, b)?,
+                _ => {}
+            }
+        }
+        Ok(self.messages.len() + self.enums.len() > known)
+    }
+
+    fn add_message(&mut self, scope: &str, data: &[u8]) -> Result<(), String> {
+        let wire = parse_wire(data).ok_or("malformed DescriptorProto")?;
+        let mut name = String::new();
+        let mut fields = Vec::new();
+        for (number, value) in &wire {
+            match (number, value) {
+                (1, Wire::Bytes(b)) => name = String::from_utf8_lossy(b).into_owned(),
+                (2, Wire::Bytes(b)) => fields.push(parse_field(b)?),
+                _ => {}
+            }
+        }
+        let full_name = qualify(scope, &name);
+
+        for (number, value) in wire {
+            match (number, value) {
+                (3, Wire::Bytes(b)) => self.add_message(&full_name, b)?,
+                (4, Wire::Bytes(b)) => self.add_enum(&full_name, b)?,
+                _ => {}
+            }
+        }
+        self.messages.insert(full_name, fields);
+        Ok(())
+    }
+
+    fn add_enum(&mut self, scope: &str, data: &[u8]) -> Result<(), String> {
+        let mut name = String::new();
+        let mut values = Vec::new();
+        for (number, wire) in parse_wire(data).ok_or("malformed EnumDescriptorProto")? {
+            match (number, wire) {
+                (1, Wire::Bytes(b)) => name = String::from_utf8_lossy(b).into_owned(),
+                (2, Wire::Bytes(b)) => {
+                    let mut value_name = String::new();
+                    let mut value_number = 0;
+                    for (number, wire) in parse_wire(b).ok_or("malformed enum value")? {
+                        match (number, wire) {
+                            (1, Wire::Bytes(b)) => {
+                                value_name = String::from_utf8_lossy(b).into_owned()
+                            }
+                            (2, Wire::Varint(v)) => value_number = v as i32 as i64,
+                            _ => {}
+                        }
+                    }
+                    values.push((value_name, value_number));
+                }
+                _ => {}
+            }
+        }
+        self.enums.insert(qualify(scope, &name), values);
+        Ok(())
+    }
+
+    /// Decodes a message into a field tree, or `None` if its descriptor isn't known
+    /// or the bytes don't match it
+    pub fn decode(&self, message: &str, data: &[u8]) -> Option<StructValue> {
+        self.decode_message(message, data, 0)
+    }
+
+    fn decode_message(&self, message: &str, data: &[u8], depth: usize) -> Option<StructValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let fields = self.messages.get(message)?;
+        let wire = parse_wire(data)?;
+
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            let mut items = Vec::new();
+            for (_, value) in wire.iter().filter(|(number, _)| *number == field.number) {
+                match value {
+                    // Repeated numbers are usually packed into one length-delimited field
+                    Wire::Bytes(packed) if field.repeated && is_packable(field.kind) => {
+                        for value in unpack(field.kind, packed)? {
+                            items.push(self.decode_field(field, value, depth)?);
+                        }
+                    }
+                    value => items.push(self.decode_field(field, *value, depth)?),
+                }
+            }
+
+            let value = if field.repeated {
+                StructValue::Array(items)
+            } else if let Some(last) = items.pop() {
+                // The last occurrence of a singular field wins
+                last
+            } else {
+                // proto3 leaves out fields at their default value
+                match self.default_value(field) {
+                    Some(value) => value,
+                    None => continue,
+                }
+            };
+            values.push((field.name.clone(), value));
+        }
+        Some(StructValue::Struct(message.to_string(), values))
+    }
+
+    fn decode_field(&self, field: &Field, wire: Wire, depth: usize) -> Option<StructValue> {
+        Some(match (field.kind, wire) {
+            (TYPE_DOUBLE, Wire::Fixed64(v)) => StructValue::Float(f64::from_bits(v)),
+            (TYPE_FLOAT, Wire::Fixed32(v)) => StructValue::Float(f32::from_bits(v) as f64),
+            (TYPE_INT64 | TYPE_INT32, Wire::Varint(v)) => StructValue::Int(v as i64),
+            (TYPE_UINT64 | TYPE_UINT32, Wire::Varint(v)) => StructValue::UInt(v),
+            (TYPE_SINT32 | TYPE_SINT64, Wire::Varint(v)) => {
+                StructValue::Int((v >> 1) as i64 ^ -((v & 1) as i64))
+            }
+            (TYPE_FIXED64, Wire::Fixed64(v)) => StructValue::UInt(v),
+            (TYPE_SFIXED64, Wire::Fixed64(v)) => StructValue::Int(v as i64),
+            (TYPE_FIXED32, Wire::Fixed32(v)) => StructValue::UInt(v as u64),
+            (TYPE_SFIXED32, Wire::Fixed32(v)) => StructValue::Int(v as i32 as i64),
+            (TYPE_BOOL, Wire::Varint(v)) => StructValue::Bool(v != 0),
+            (TYPE_ENUM, Wire::Varint(v)) => self.enum_value(&field.type_name, v as i32 as i64),
+            (TYPE_STRING, Wire::Bytes(b)) => {
+                StructValue::String(String::from_utf8_lossy(b).into_owned())
+            }
+            (TYPE_BYTES, Wire::Bytes(b)) => StructValue::String(format!("<{} bytes>", b.len())),
+            (TYPE_MESSAGE, Wire::Bytes(b)) => {
+                self.decode_message(&field.type_name, b, depth + 1)?
+            }
+            _ => return None,
+        })
+    }
+
+    fn enum_value(&self, enum_name: &str, number: i64) -> StructValue {
+        let name = self
+            .enums
+            .get(enum_name)
+            .and_then(|values| values.iter().find(|(_, v)| *v == number))
+            .map(|(name, _)| name.clone());
+        StructValue::Enum(name, number)
+    }
+
+    fn default_value(&self, field: &Field) -> Option<StructValue> {
+        Some(match field.kind {
+            TYPE_DOUBLE | TYPE_FLOAT => StructValue::Float(0.0),
+            TYPE_INT64 | TYPE_INT32 | TYPE_SINT32 | TYPE_SINT64 | TYPE_SFIXED32 | TYPE_SFIXED64 => {
+                StructValue::Int(0)
+            }
+            TYPE_UINT64 | TYPE_UINT32 | TYPE_FIXED64 | TYPE_FIXED32 => StructValue::UInt(0),
+            TYPE_BOOL => StructValue::Bool(false),
+            TYPE_STRING => StructValue::String(String::new()),
+            TYPE_BYTES => StructValue::String("<0 bytes>".to_string()),
+            TYPE_ENUM => self.enum_value(&field.type_name, 0),
+            // Unset sub-messages are just absent
+            _ => return None,
+        })
+    }
+}
+
+fn parse_field(data: &[u8]) -> Result<Field, String> {
+    let mut field = Field {
+        name: String::new(),
+        number: 0,
+        kind: 0,
+        repeated: false,
+        type_name: String::new(),
+    };
+    for (number, wire) in parse_wire(data).ok_or("malformed FieldDescriptorProto")? {
+        match (number, wire) {
+            (1, Wire::Bytes(b)) => field.name = String::from_utf8_lossy(b).into_owned(),
+            (3, Wire::Varint(v)) => field.number = v,
+            (4, Wire::Varint(v)) => field.repeated = v == LABEL_REPEATED,
+            (5, Wire::Varint(v)) => field.kind = v,
+            // Type names are fully qualified with a leading dot, e.g. ".wpi.proto.ProtobufRotation2d"
+            (6, Wire::Bytes(b)) => {
+                field.type_name = String::from_utf8_lossy(b)
+                    .trim_start_matches('.')
+                    .to_string()
+            }
+            _ => {}
+        }
+    }
+    Ok(field)
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+fn is_packable(kind: u64) -> bool {
+    !matches!(kind, TYPE_STRING | TYPE_BYTES | TYPE_MESSAGE)
+}
+
+/// Splits a packed repeated field into its elements
+fn unpack(kind: u64, mut data: &[u8]) -> Option<Vec<Wire<'_>>> {
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        values.push(match kind {
+            TYPE_DOUBLE | TYPE_FIXED64 | TYPE_SFIXED64 => {
+                let (bytes, rest) = data.split_at_checked(8)?;
+                data = rest;
+                Wire::Fixed64(u64::from_le_bytes(bytes.try_into().ok()?))
+            }
+            TYPE_FLOAT | TYPE_FIXED32 | TYPE_SFIXED32 => {
+                let (bytes, rest) = data.split_at_checked(4)?;
+                data = rest;
+                Wire::Fixed32(u32::from_le_bytes(bytes.try_into().ok()?))
+            }
+            _ => Wire::Varint(read_varint(&mut data)?),
+        });
+    }
+    Some(values)
+}
+
+/// Splits a message into (field number, value) pairs, in the order they were written
+fn parse_wire(mut data: &[u8]) -> Option<Vec<(u64, Wire<'_>)>> {
+    let mut fields = Vec::new();
+    while !data.is_empty() {
+        let key = read_varint(&mut data)?;
+        let value = match key & 0x7 {
+            0 => Wire::Varint(read_varint(&mut data)?),
+            1 => {
+                let (bytes, rest) = data.split_at_checked(8)?;
+                data = rest;
+                Wire::Fixed64(u64::from_le_bytes(bytes.try_into().ok()?))
+            }
+            2 => {
+                let len = read_varint(&mut data)? as usize;
+                let (bytes, rest) = data.split_at_checked(len)?;
+                data = rest;
+                Wire::Bytes(bytes)
+            }
+            5 => {
+                let (bytes, rest) = data.split_at_checked(4)?;
+                data = rest;
+                Wire::Fixed32(u32::from_le_bytes(bytes.try_into().ok()?))
+            }
+            // Groups are long deprecated and nothing we care about uses them
+            _ => return None,
+        };
+        fields.push((key >> 3, value));
+    }
+    Some(fields)
+}
+
+fn read_varint(data: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = data.split_first()?;
+        *data = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        while value >= 0x80 {
+            out.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+        out
+    }
+
+    fn varint_field(number: u64, value: u64) -> Vec<u8> {
+        let mut out = varint(number << 3);
+        out.extend(varint(value));
+        out
+    }
+
+    fn bytes_field(number: u64, bytes: &[u8]) -> Vec<u8> {
+        let mut out = varint(number << 3 | 2);
+        out.extend(varint(bytes.len() as u64));
+        out.extend(bytes);
+        out
+    }
+
+    fn double_field(number: u64, value: f64) -> Vec<u8> {
+        let mut out = varint(number << 3 | 1);
+        out.extend(value.to_le_bytes());
+        out
+    }
+
+    fn field(name: &str, number: u64, kind: u64, repeated: bool, type_name: &str) -> Vec<u8> {
+        let mut out = bytes_field(1, name.as_bytes());
+        out.extend(varint_field(3, number));
+        out.extend(varint_field(4, if repeated { LABEL_REPEATED } else { 1 }));
+        out.extend(varint_field(5, kind));
+        if !type_name.is_empty() {
+            out.extend(bytes_field(6, type_name.as_bytes()));
+        }
+        out
+    }
+
+    fn message(name: &str, fields: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = bytes_field(1, name.as_bytes());
+        for field in fields {
+            out.extend(bytes_field(2, field));
+        }
+        out
+    }
+
+    fn file(package: &str, messages: &[Vec<u8>], enums: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = bytes_field(2, package.as_bytes());
+        for message in messages {
+            out.extend(bytes_field(4, message));
+        }
+        for e in enums {
+            out.extend(bytes_field(5, e));
+        }
+        out
+    }
+
+    fn zigzag(value: i64) -> u64 {
+        ((value << 1) ^ (value >> 63)) as u64
+    }
+
+    fn geometry_file() -> Vec<u8> {
+        let translation = message(
+            "ProtobufTranslation2d",
+            &[
+                field("x", 1, TYPE_DOUBLE, false, ""),
+                field("y", 2, TYPE_DOUBLE, false, ""),
+            ],
+        );
+        let rotation = message(
+            "ProtobufRotation2d",
+            &[field("value", 1, TYPE_DOUBLE, false, "")],
+        );
+        let pose = message(
+            "ProtobufPose2d",
+            &[
+                field(
+                    "translation",
+                    1,
+                    TYPE_MESSAGE,
+                    false,
+                    ".wpi.proto.ProtobufTranslation2d",
+                ),
+                field(
+                    "rotation",
+                    2,
+                    TYPE_MESSAGE,
+                    false,
+                    ".wpi.proto.ProtobufRotation2d",
+                ),
+            ],
+        );
+        file("wpi.proto", &[translation, rotation, pose], &[])
+    }
+
+    fn pose_payload(x: f64, y: f64, rotation: f64) -> Vec<u8> {
+        let mut translation = double_field(1, x);
+        translation.extend(double_field(2, y));
+        let mut out = bytes_field(1, &translation);
+        out.extend(bytes_field(2, &double_field(1, rotation)));
+        out
+    }
+
+    #[test]
+    fn decodes_pose2d() {
+        let mut registry = ProtoRegistry::default();
+        assert_eq!(registry.add_file(&geometry_file()), Ok(true));
+        assert_eq!(registry.add_file(&geometry_file()), Ok(false));
+
+        let decoded = registry
+            .decode("wpi.proto.ProtobufPose2d", &pose_payload(1.5, -2.0, 0.25))
+            .expect("pose should decode");
+        assert_eq!(
+            decoded.to_json(),
+            json!({"translation": {"x": 1.5, "y": -2.0}, "rotation": {"value": 0.25}})
+        );
+    }
+
+    #[test]
+    fn fills_in_proto3_defaults() {
+        let mut registry = ProtoRegistry::default();
+        registry.add_file(&geometry_file()).unwrap();
+
+        // x is left out because it's zero, rotation because it's unset
+        let payload = bytes_field(1, &double_field(2, 3.0));
+        let decoded = registry.decode("wpi.proto.ProtobufPose2d", &payload);
+        assert_eq!(
+            decoded.map(|v| v.to_json()),
+            Some(json!({"translation": {"x": 0.0, "y": 3.0}}))
+        );
+    }
+
+    #[test]
+    fn decodes_packed_repeated_and_zigzag_fields() {
+        let mode = {
+            let mut out = bytes_field(1, b"Mode");
+            for (name, number) in [("OFF", 0), ("ON", 1)] {
+                let mut value = bytes_field(1, name.as_bytes());
+                value.extend(varint_field(2, number));
+                out.extend(bytes_field(2, &value));
+            }
+            out
+        };
+        let samples = message(
+            "Samples",
+            &[
+                field("values", 1, TYPE_DOUBLE, true, ""),
+                field("deltas", 2, TYPE_SINT32, true, ""),
+                field("offset", 3, TYPE_SINT64, false, ""),
+                field("mode", 4, TYPE_ENUM, false, ".test.Mode"),
+            ],
+        );
+        let mut registry = ProtoRegistry::default();
+        registry
+            .add_file(&file("test", &[samples], &[mode]))
+            .unwrap();
+
+        let values: Vec<u8> = [1.0f64, 2.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let deltas: Vec<u8> = [-1, 1, -2]
+            .iter()
+            .flat_map(|&d| varint(zigzag(d)))
+            .collect();
+        let mut payload = bytes_field(1, &values);
+        payload.extend(bytes_field(2, &deltas));
+        payload.extend(varint_field(3, zigzag(-300)));
+        payload.extend(varint_field(4, 1));
+
+        let decoded = registry.decode("test.Samples", &payload).unwrap();
+        assert_eq!(
+            decoded,
+            StructValue::Struct(
+                "test.Samples".to_string(),
+                vec![
+                    (
+                        "values".to_string(),
+                        StructValue::Array(vec![StructValue::Float(1.0), StructValue::Float(2.5)])
+                    ),
+                    (
+                        "deltas".to_string(),
+                        StructValue::Array(vec![
+                            StructValue::Int(-1),
+                            StructValue::Int(1),
+                            StructValue::Int(-2)
+                        ])
+                    ),
+                    ("offset".to_string(), StructValue::Int(-300)),
+                    (
+                        "mode".to_string(),
+                        StructValue::Enum(Some("ON".to_string()), 1)
+                    ),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn missing_descriptors_dont_decode() {
+        let mut registry = ProtoRegistry::default();
+        assert_eq!(registry.decode("wpi.proto.ProtobufPose2d", &[]), None);
+
+        // The pose references messages this file doesn't define
+        let pose = message(
+            "ProtobufPose2d",
+            &[field(
+                "translation",
+                1,
+                TYPE_MESSAGE,
+                false,
+                ".wpi.proto.ProtobufTranslation2d",
+            )],
+        );
+        registry.add_file(&file("wpi.proto", &[pose], &[])).unwrap();
+        let payload = pose_payload(1.0, 2.0, 3.0);
+        assert_eq!(registry.decode("wpi.proto.ProtobufPose2d", &payload), None);
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        let mut registry = ProtoRegistry::default();
+        assert!(registry.add_file(&[0x0a, 0x05, b'a']).is_err());
+
+        registry.add_file(&geometry_file()).unwrap();
+        assert_eq!(
+            registry.decode("wpi.proto.ProtobufRotation2d", &[0x09, 0, 0]),
+            None
+        );
+    }
+}
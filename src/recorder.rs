@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::nt::NtUpdate;
+use crate::value::{NtType, NtValue};
+
+// Control record types, sent on entry 0
+pub const CONTROL_START: u8 = 0;
+pub const CONTROL_FINISH: u8 = 1;
+
+/// Writes NT traffic to a WPILib DataLog (.wpilog) file.
+/// See https://github.com/wpilibsuite/allwpilib/blob/main/wpiutil/doc/datalog.adoc
+pub struct Recorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    // Entry id and type of each topic that has a started entry
+    entries: HashMap<String, (u32, NtType)>,
+    next_id: u32,
+    start: Instant,
+    bytes: u64,
+    // Updates that were dropped before they could be written
+    missed: u64,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let extra_header = b"dash89";
+        writer.write_all(b"WPILOG")?;
+        writer.write_all(&0x0100u16.to_le_bytes())?;
+        writer.write_all(&(extra_header.len() as u32).to_le_bytes())?;
+        writer.write_all(extra_header)?;
+
+        Ok(Recorder {
+            writer,
+            path: path.to_path_buf(),
+            entries: HashMap::new(),
+            next_id: 1,
+            start: Instant::now(),
+            bytes: 12 + extra_header.len() as u64,
+            missed: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Seconds since recording started
+    pub fn elapsed(&self) -> f64 {
+        self.start.elapsed().as_secs_f64()
+    }
+
+    /// Bytes written so far
+    pub fn size(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Updates that never made it into the file because the recording fell behind
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    pub fn add_missed(&mut self, count: u64) {
+        self.missed += count;
+    }
+
+    pub fn record(&mut self, update: &NtUpdate) -> io::Result<()> {
+        match update {
+            NtUpdate::Announced(topic, ty) => self.start_entry(topic, ty),
+            NtUpdate::Updated(topic, value) => self.record_value(topic, value),
+            NtUpdate::Unannounced(topic) => self.finish_entry(topic),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes a value, starting its entry first if the topic was announced before recording began
+    pub fn record_value(&mut self, topic: &str, value: &NtValue) -> io::Result<()> {
+        let ty = value.nt_type();
+        let id = match self.entries.get(topic) {
+            Some((id, entry_ty)) if *entry_ty == ty => *id,
+            // Type changed under us, so start a fresh entry for the new type
+            _ => {
+                self.start_entry(topic, &ty)?;
+                self.entries[topic].0
+            }
+        };
+        let payload = encode_value(value);
+        self.write_record(id, &payload)
+    }
+
+    pub fn start_entry(&mut self, topic: &str, ty: &NtType) -> io::Result<()> {
+        if let Some((_, entry_ty)) = self.entries.get(topic) {
+            if entry_ty == ty {
+                return Ok(());
+            }
+            self.finish_entry(topic)?;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.insert(topic.to_string(), (id, ty.clone()));
+
+        let type_name = log_type_name(ty);
+        let mut payload = vec![CONTROL_START];
+        payload.extend_from_slice(&id.to_le_bytes());
+        write_string(&mut payload, topic);
+        write_string(&mut payload, type_name);
+        write_string(&mut payload, "");
+        self.write_record(0, &payload)
+    }
+
+    pub fn finish_entry(&mut self, topic: &str) -> io::Result<()> {
+        let Some((id, _)) = self.entries.remove(topic) else {
+            return Ok(());
+        };
+        let mut payload = vec![CONTROL_FINISH];
+        payload.extend_from_slice(&id.to_le_bytes());
+        self.write_record(0, &payload)
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn write_record(&mut self, id: u32, payload: &[u8]) -> io::Result<()> {
+        let timestamp = self.start.elapsed().as_micros() as u64;
+
+        let id_bytes = &id.to_le_bytes()[..min_len(id as u64, 4)];
+        let size = payload.len() as u64;
+        let size_bytes = &size.to_le_bytes()[..min_len(size, 4)];
+        let time_bytes = &timestamp.to_le_bytes()[..min_len(timestamp, 8)];
+
+        // Each field is stored in as few bytes as it fits, with the lengths packed into the first byte
+        let header = (id_bytes.len() - 1) as u8
+            | ((size_bytes.len() - 1) as u8) << 2
+            | ((time_bytes.len() - 1) as u8) << 4;
+
+        self.writer.write_all(&[header])?;
+        self.writer.write_all(id_bytes)?;
+        self.writer.write_all(size_bytes)?;
+        self.writer.write_all(time_bytes)?;
+        self.writer.write_all(payload)?;
+        self.bytes += (1 + id_bytes.len() + size_bytes.len() + time_bytes.len()) as u64 + size;
+        Ok(())
+    }
+}
+
+/// File name stamped with the current time, e.g. `dash89_2025-03-14_18-30-05.wpilog` (UTC)
+pub fn timestamped_file_name(extension: &str) -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let time = secs % 86400;
+    format!(
+        "dash89_{:04}-{:02}-{:02}_{:02}-{:02}-{:02}.{}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time / 60 % 60,
+        time % 60,
+        extension
+    )
+}
+
+// Days since 1970-01-01 to a (year, month, day) date, from Howard Hinnant's date algorithms
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn min_len(value: u64, max: usize) -> usize {
+    let bytes = (64 - value.leading_zeros() as usize).div_ceil(8);
+    bytes.clamp(1, max)
+}
+
+fn write_string(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+// DataLog calls NT's int "int64"; everything else keeps its NT type string
+fn log_type_name(ty: &NtType) -> &str {
+    match ty {
+        NtType::Int => "int64",
+        NtType::IntArray => "int64[]",
+        _ => ty.as_str(),
+    }
+}
+
+fn encode_value(value: &NtValue) -> Vec<u8> {
+    match value {
+        NtValue::Boolean(b) => vec![*b as u8],
+        NtValue::Int(i) => i.to_le_bytes().to_vec(),
+        NtValue::Float(f) => f.to_le_bytes().to_vec(),
+        NtValue::Double(d) => d.to_le_bytes().to_vec(),
+        NtValue::String(s) | NtValue::Json(s) => s.as_bytes().to_vec(),
+        NtValue::Raw(bytes) => bytes.clone(),
+        NtValue::BooleanArray(v) => v.iter().map(|b| *b as u8).collect(),
+        NtValue::IntArray(v) => v.iter().flat_map(|i| i.to_le_bytes()).collect(),
+        NtValue::FloatArray(v) => v.iter().flat_map(|f| f.to_le_bytes()).collect(),
+        NtValue::DoubleArray(v) => v.iter().flat_map(|d| d.to_le_bytes()).collect(),
+        NtValue::StringArray(v) => {
+            let mut buf = (v.len() as u32).to_le_bytes().to_vec();
+            for s in v {
+                write_string(&mut buf, s);
+            }
+            buf
+        }
+        // struct:, proto: and friends arrive as binary; anything else is kept as msgpack
+        NtValue::Other(_, rmpv::Value::Binary(bytes)) | NtValue::Struct(_, bytes, _) => {
+            bytes.clone()
+        }
+        NtValue::Other(_, value) => {
+            let mut buf = Vec::new();
+            let _ = rmpv::encode::write_value(&mut buf, value);
+            buf
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playback::read_log;
+    use rmpv::Value;
+
+    #[test]
+    fn round_trips_through_read_log() {
+        let path = std::env::temp_dir().join(format!("dash89_test_{}.wpilog", std::process::id()));
+        let pose = NtType::Other("struct:Pose2d".to_string());
+        let written = vec![
+            NtUpdate::Announced("/speed".to_string(), NtType::Double),
+            NtUpdate::Updated("/speed".to_string(), NtValue::Double(1.5)),
+            NtUpdate::Updated("/count".to_string(), NtValue::Int(-42)),
+            NtUpdate::Updated(
+                "/names".to_string(),
+                NtValue::StringArray(vec!["a".to_string(), "b, c".to_string()]),
+            ),
+            NtUpdate::Updated(
+                "/flags".to_string(),
+                NtValue::BooleanArray(vec![true, false]),
+            ),
+            NtUpdate::Updated(
+                "/pose".to_string(),
+                NtValue::Other(pose.clone(), Value::Binary(vec![1, 2, 3])),
+            ),
+            // A type change starts a new entry for the topic
+            NtUpdate::Updated("/speed".to_string(), NtValue::String("fast".to_string())),
+            NtUpdate::Unannounced("/count".to_string()),
+        ];
+
+        let mut recorder = Recorder::create(&path).unwrap();
+        for update in &written {
+            recorder.record(update).unwrap();
+        }
+        let size = recorder.size();
+        recorder.finish().unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), size);
+
+        let read = read_log(&path);
+        std::fs::remove_file(&path).unwrap();
+        let read: Vec<String> = read
+            .unwrap()
+            .iter()
+            .map(|(_, update)| format!("{:?}", update))
+            .collect();
+
+        let expected: Vec<String> = [
+            NtUpdate::Announced("/speed".to_string(), NtType::Double),
+            NtUpdate::Updated("/speed".to_string(), NtValue::Double(1.5)),
+            NtUpdate::Announced("/count".to_string(), NtType::Int),
+            NtUpdate::Updated("/count".to_string(), NtValue::Int(-42)),
+            NtUpdate::Announced("/names".to_string(), NtType::StringArray),
+            NtUpdate::Updated(
+                "/names".to_string(),
+                NtValue::StringArray(vec!["a".to_string(), "b, c".to_string()]),
+            ),
+            NtUpdate::Announced("/flags".to_string(), NtType::BooleanArray),
+            NtUpdate::Updated(
+                "/flags".to_string(),
+                NtValue::BooleanArray(vec![true, false]),
+            ),
+            NtUpdate::Announced("/pose".to_string(), pose.clone()),
+            NtUpdate::Updated(
+                "/pose".to_string(),
+                NtValue::Other(pose, Value::Binary(vec![1, 2, 3])),
+            ),
+            NtUpdate::Unannounced("/speed".to_string()),
+            NtUpdate::Announced("/speed".to_string(), NtType::String),
+            NtUpdate::Updated("/speed".to_string(), NtValue::String("fast".to_string())),
+            NtUpdate::Unannounced("/count".to_string()),
+        ]
+        .iter()
+        .map(|update| format!("{:?}", update))
+        .collect();
+        assert_eq!(read, expected);
+    }
+
+    #[test]
+    fn stores_fields_in_as_few_bytes_as_fit() {
+        assert_eq!(min_len(0, 4), 1);
+        assert_eq!(min_len(0xff, 4), 1);
+        assert_eq!(min_len(0x100, 4), 2);
+        assert_eq!(min_len(u64::MAX, 8), 8);
+    }
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(20161), (2025, 3, 14));
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use rmpv::Value;
+use serde_json::json;
+
+use crate::protobuf::ProtoRegistry;
+use crate::value::{NtType, NtValue};
+
+const SCHEMA_PREFIX: &str = "/.schema/struct:";
+const PROTO_SCHEMA_PREFIX: &str = "/.schema/proto:";
+
+// Structs nested deeper than this are assumed to be a bad or recursive schema
+const MAX_DEPTH: usize = 32;
+
+/// Struct and protobuf schemas published by the robot, used to decode `struct:` and
+/// `proto:` topics. See https://github.com/wpilibsuite/allwpilib/blob/main/wpiutil/doc/struct.adoc
+#[derive(Default)]
+pub struct StructRegistry {
+    schemas: HashMap<String, Vec<Field>>,
+    protos: ProtoRegistry,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    kind: Kind,
+    /// Fixed array length, if the field is an array
+    array: Option<usize>,
+    /// Width in bits, if the field is a bit-field
+    bits: Option<u32>,
+    enum_values: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Bool,
+    Char,
+    Int(usize),
+    UInt(usize),
+    Float32,
+    Float64,
+    Struct(String),
+}
+
+/// A decoded struct or protobuf message, or one of its fields
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    /// Enum field value with its name, if it has one
+    Enum(Option<String>, i64),
+    Array(Vec<StructValue>),
+    Struct(String, Vec<(String, StructValue)>),
+}
+
+impl StructRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Picks up schemas from `/.schema/struct:*` and `/.schema/proto:*` topics.
+    /// Returns true if a new schema was added.
+    pub fn observe(&mut self, topic: &str, value: &NtValue) -> bool {
+        if !topic.starts_with(SCHEMA_PREFIX) && !topic.starts_with(PROTO_SCHEMA_PREFIX) {
+            return false;
+        }
+        let data = match value {
+            NtValue::Raw(bytes) | NtValue::Other(_, Value::Binary(bytes)) => bytes.as_slice(),
+            NtValue::String(text) => text.as_bytes(),
+            _ => return false,
+        };
+
+        if let Some(file) = topic.strip_prefix(PROTO_SCHEMA_PREFIX) {
+            return match self.protos.add_file(data) {
+                Ok(added) => {
+                    log::debug!("Registered protobuf descriptors from {}", file);
+                    added
+                }
+                Err(e) => {
+                    log::warn!("Invalid protobuf descriptor {}: {}", file, e);
+                    false
+                }
+            };
+        }
+
+        let name = &topic[SCHEMA_PREFIX.len()..];
+        let text = String::from_utf8_lossy(data);
+        match parse_schema(&text) {
+            Ok(fields) => {
+                log::debug!("Registered struct schema {}: {}", name, text);
+                self.schemas.insert(name.to_string(), fields).is_none()
+            }
+            Err(e) => {
+                log::warn!("Invalid struct schema for {}: {}", name, e);
+                false
+            }
+        }
+    }
+
+    /// Decodes `struct:` and `proto:` values whose schema is known; anything else is
+    /// returned unchanged
+    pub fn decode(&self, value: NtValue) -> NtValue {
+        let NtValue::Other(NtType::Other(ty), Value::Binary(bytes)) = &value else {
+            return value;
+        };
+
+        let decoded = if let Some(message) = ty.strip_prefix("proto:") {
+            self.protos.decode(message, bytes)
+        } else if let Some(name) = ty.strip_prefix("struct:") {
+            self.decode_structs(name, bytes)
+        } else {
+            None
+        };
+        match decoded {
+            Some(decoded) => NtValue::Struct(NtType::Other(ty.clone()), bytes.clone(), decoded),
+            None => value,
+        }
+    }
+
+    /// Decodes a `struct:Name` or `struct:Name[]` value
+    fn decode_structs(&self, name: &str, bytes: &[u8]) -> Option<StructValue> {
+        let mut reader = Reader::new(bytes);
+        match name.strip_suffix("[]") {
+            Some(name) => {
+                let mut items = Vec::new();
+                while !reader.is_empty() {
+                    let remaining = reader.len();
+                    items.push(self.decode_struct(name, &mut reader, 0)?);
+                    // A struct with no fields would never finish the array
+                    if reader.len() == remaining {
+                        return None;
+                    }
+                }
+                Some(StructValue::Array(items))
+            }
+            None => self.decode_struct(name, &mut reader, 0),
+        }
+    }
+
+    fn decode_struct(&self, name: &str, reader: &mut Reader, depth: usize) -> Option<StructValue> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let fields = self.schemas.get(name)?;
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            let value = match field.array {
+                // char arrays are fixed-size strings
+                Some(len) if field.kind == Kind::Char => {
+                    reader.end_bits();
+                    let bytes = reader.bytes(len)?;
+                    let text = String::from_utf8_lossy(bytes);
+                    StructValue::String(text.trim_end_matches('\0').to_string())
+                }
+                Some(len) => StructValue::Array(
+                    (0..len)
+                        .map(|_| self.decode_field(field, reader, depth))
+                        .collect::<Option<_>>()?,
+                ),
+                None => self.decode_field(field, reader, depth)?,
+            };
+            values.push((field.name.clone(), value));
+        }
+        reader.end_bits();
+        Some(StructValue::Struct(name.to_string(), values))
+    }
+
+    fn decode_field(
+        &self,
+        field: &Field,
+        reader: &mut Reader,
+        depth: usize,
+    ) -> Option<StructValue> {
+        if let Some(bits) = field.bits {
+            let raw = reader.bits(&field.kind, bits)?;
+            let value = match field.kind {
+                Kind::Bool => StructValue::Bool(raw != 0),
+                // Sign-extend from the top bit of the field
+                Kind::Int(_) => StructValue::Int(((raw << (64 - bits)) as i64) >> (64 - bits)),
+                _ => StructValue::UInt(raw),
+            };
+            return Some(with_enum(field, value));
+        }
+
+        reader.end_bits();
+        let value = match &field.kind {
+            Kind::Bool => StructValue::Bool(reader.uint(1)? != 0),
+            Kind::Char => StructValue::String((reader.uint(1)? as u8 as char).to_string()),
+            Kind::Int(size) => {
+                let raw = reader.uint(*size)?;
+                let shift = 64 - size * 8;
+                StructValue::Int(((raw << shift) as i64) >> shift)
+            }
+            Kind::UInt(size) => StructValue::UInt(reader.uint(*size)?),
+            Kind::Float32 => StructValue::Float(f32::from_bits(reader.uint(4)? as u32) as f64),
+            Kind::Float64 => StructValue::Float(f64::from_bits(reader.uint(8)?)),
+            Kind::Struct(name) => self.decode_struct(name, reader, depth + 1)?,
+        };
+        Some(with_enum(field, value))
+    }
+}
+
+fn with_enum(field: &Field, value: StructValue) -> StructValue {
+    if field.enum_values.is_empty() {
+        return value;
+    }
+    let number = match value {
+        StructValue::Int(i) => i,
+        StructValue::UInt(u) => u as i64,
+        _ => return value,
+    };
+    let name = field
+        .enum_values
+        .iter()
+        .find(|(_, v)| *v == number)
+        .map(|(name, _)| name.clone());
+    StructValue::Enum(name, number)
+}
+
+fn parse_schema(text: &str) -> Result<Vec<Field>, String> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|decl| !decl.is_empty())
+        .map(parse_field)
+        .collect()
+}
+
+// e.g. "double x", "int8 data[4]", "uint8 flag:1", "enum {a=1, b=2} int8 val"
+fn parse_field(decl: &str) -> Result<Field, String> {
+    let mut rest = decl;
+    let mut enum_values = Vec::new();
+    if let Some(after) = rest.strip_prefix("enum") {
+        let after = after.trim_start();
+        let body_end = after
+            .strip_prefix('{')
+            .and_then(|s| s.find('}').map(|end| (s, end)))
+            .ok_or_else(|| format!("malformed enum in '{}'", decl))?;
+        let (body, end) = body_end;
+        for item in body[..end]
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            let (name, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("enum value '{}' has no number", item))?;
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|_| format!("enum value '{}' is not a number", item))?;
+            enum_values.push((name.trim().to_string(), value));
+        }
+        rest = body[end + 1..].trim_start();
+    }
+
+    let (type_name, name_spec) = rest
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| format!("missing field name in '{}'", decl))?;
+    let kind = match type_name {
+        "bool" => Kind::Bool,
+        "char" => Kind::Char,
+        "int8" => Kind::Int(1),
+        "int16" => Kind::Int(2),
+        "int32" => Kind::Int(4),
+        "int64" => Kind::Int(8),
+        "uint8" => Kind::UInt(1),
+        "uint16" => Kind::UInt(2),
+        "uint32" => Kind::UInt(4),
+        "uint64" => Kind::UInt(8),
+        "float" | "float32" => Kind::Float32,
+        "double" | "float64" => Kind::Float64,
+        other => Kind::Struct(other.to_string()),
+    };
+
+    let mut name = name_spec.trim();
+    let mut array = None;
+    let mut bits = None;
+    if let Some((field_name, len)) = name.split_once('[') {
+        let len = len
+            .trim_end_matches(']')
+            .trim()
+            .parse()
+            .map_err(|_| format!("bad array length in '{}'", decl))?;
+        array = Some(len);
+        name = field_name.trim();
+    } else if let Some((field_name, width)) = name.split_once(':') {
+        let width: u32 = width
+            .trim()
+            .parse()
+            .map_err(|_| format!("bad bit-field width in '{}'", decl))?;
+        let max = match kind {
+            Kind::Bool => 1,
+            Kind::Int(size) | Kind::UInt(size) => size as u32 * 8,
+            _ => return Err(format!("'{}' can't be a bit-field", type_name)),
+        };
+        if width == 0 || width > max {
+            return Err(format!("bit-field width out of range in '{}'", decl));
+        }
+        bits = Some(width);
+        name = field_name.trim();
+    }
+
+    Ok(Field {
+        name: name.to_string(),
+        kind,
+        array,
+        bits,
+        enum_values,
+    })
+}
+
+/// Little-endian reader that also tracks the storage unit of consecutive bit-fields
+struct Reader<'a> {
+    data: &'a [u8],
+    /// Value, size in bytes and bits used of the current bit-field storage unit
+    unit: Option<(u64, usize, u32)>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, unit: None }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    fn uint(&mut self, size: usize) -> Option<u64> {
+        let bytes = self.bytes(size)?;
+        Some(bytes.iter().rev().fold(0, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    /// Takes `width` bits for a field of `kind`, continuing the current storage unit if
+    /// it's the same size and has room (bools fit in any unit), or starting a new one
+    fn bits(&mut self, kind: &Kind, width: u32) -> Option<u64> {
+        let size = match kind {
+            Kind::Int(size) | Kind::UInt(size) => *size,
+            _ => 1,
+        };
+        let fits = |(_, unit_size, used): (u64, usize, u32)| {
+            (unit_size == size || *kind == Kind::Bool) && used + width <= unit_size as u32 * 8
+        };
+        let (value, unit_size, used) = match self.unit {
+            Some(unit) if fits(unit) => unit,
+            _ => (self.uint(size)?, size, 0),
+        };
+        self.unit = Some((value, unit_size, used + width));
+        let mask = if width == 64 {
+            u64::MAX
+        } else {
+            (1 << width) - 1
+        };
+        Some((value >> used) & mask)
+    }
+
+    /// Any field that isn't a bit-field closes the current storage unit
+    fn end_bits(&mut self) {
+        self.unit = None;
+    }
+}
+
+impl StructValue {
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            StructValue::Bool(b) => json!(b),
+            StructValue::Int(i) => json!(i),
+            StructValue::UInt(u) => json!(u),
+            StructValue::Float(f) => json!(f),
+            StructValue::String(s) => json!(s),
+            StructValue::Enum(Some(name), _) => json!(name),
+            StructValue::Enum(None, number) => json!(number),
+            StructValue::Array(items) => items.iter().map(StructValue::to_json).collect(),
+            StructValue::Struct(_, fields) => fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_json()))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        }
+    }
+
+    // Nested structs are flattened into their parent so a Pose2d reads
+    // "x: 1.23 y: 4.56 rotation: 90°" instead of a pile of braces
+    fn write_fields(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        match self {
+            StructValue::Struct(ty, fields) if fields.len() > 1 || !is_rotation(ty) => {
+                for (i, (field_name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    value.write_fields(f, field_name)?;
+                }
+                Ok(())
+            }
+            _ => write!(f, "{}: {}", name, self),
+        }
+    }
+}
+
+fn is_rotation(ty: &str) -> bool {
+    ty == "Rotation2d" || ty == "wpi.proto.ProtobufRotation2d"
+}
+
+impl fmt::Display for StructValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructValue::Bool(b) => write!(f, "{}", b),
+            StructValue::Int(i) => write!(f, "{}", i),
+            StructValue::UInt(u) => write!(f, "{}", u),
+            StructValue::Float(v) => write!(f, "{:.2}", v),
+            StructValue::String(s) => write!(f, "{}", s),
+            StructValue::Enum(Some(name), _) => write!(f, "{}", name),
+            StructValue::Enum(None, number) => write!(f, "{}", number),
+            StructValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if matches!(item, StructValue::Struct(..)) {
+                        write!(f, "{{{}}}", item)?;
+                    } else {
+                        write!(f, "{}", item)?;
+                    }
+                }
+                write!(f, "]")
+            }
+            // Rotations are stored in radians; degrees are easier to read at a glance
+            StructValue::Struct(ty, fields) if is_rotation(ty) && fields.len() == 1 => {
+                match &fields[0].1 {
+                    StructValue::Float(radians) => write!(f, "{:.1}°", radians.to_degrees()),
+                    value => write!(f, "{}", value),
+                }
+            }
+            StructValue::Struct(..) => self.write_fields(f, ""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(schemas: &[(&str, &str)]) -> StructRegistry {
+        let mut registry = StructRegistry::new();
+        for (name, schema) in schemas {
+            let value = NtValue::Other(
+                NtType::from_type_str("structschema"),
+                Value::Binary(schema.as_bytes().to_vec()),
+            );
+            registry.observe(&format!("{}{}", SCHEMA_PREFIX, name), &value);
+        }
+        registry
+    }
+
+    fn pose_registry() -> StructRegistry {
+        registry(&[
+            ("Translation2d", "double x;double y"),
+            ("Rotation2d", "double value"),
+            ("Pose2d", "Translation2d translation;Rotation2d rotation"),
+        ])
+    }
+
+    fn doubles(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn decode(registry: &StructRegistry, ty: &str, bytes: Vec<u8>) -> Option<StructValue> {
+        let value = NtValue::decode(&NtType::from_type_str(ty), Value::Binary(bytes));
+        match registry.decode(value) {
+            NtValue::Struct(_, _, decoded) => Some(decoded),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn decodes_nested_structs() {
+        let decoded = decode(&pose_registry(), "struct:Pose2d", doubles(&[1.5, 2.0, 0.5]));
+        assert_eq!(
+            decoded,
+            Some(StructValue::Struct(
+                "Pose2d".to_string(),
+                vec![
+                    (
+                        "translation".to_string(),
+                        StructValue::Struct(
+                            "Translation2d".to_string(),
+                            vec![
+                                ("x".to_string(), StructValue::Float(1.5)),
+                                ("y".to_string(), StructValue::Float(2.0)),
+                            ],
+                        ),
+                    ),
+                    (
+                        "rotation".to_string(),
+                        StructValue::Struct(
+                            "Rotation2d".to_string(),
+                            vec![("value".to_string(), StructValue::Float(0.5))],
+                        ),
+                    ),
+                ],
+            ))
+        );
+    }
+
+    #[test]
+    fn decodes_bit_fields() {
+        let flags = registry(&[("Flags", "uint8 a:3;int8 b:4;uint16 c")]);
+        let decoded = decode(&flags, "struct:Flags", vec![0b0111_1101, 0x34, 0x12]);
+        assert_eq!(
+            decoded,
+            Some(StructValue::Struct(
+                "Flags".to_string(),
+                vec![
+                    ("a".to_string(), StructValue::UInt(5)),
+                    ("b".to_string(), StructValue::Int(-1)),
+                    ("c".to_string(), StructValue::UInt(0x1234)),
+                ],
+            ))
+        );
+
+        // Only bools share a storage unit of another size; a 1-bit uint8 starts its own byte
+        let mixed = registry(&[("Mixed", "uint16 a:4;bool b:1;uint8 c:1")]);
+        let decoded = decode(&mixed, "struct:Mixed", vec![0b0001_0011, 0x00, 0x01]);
+        assert_eq!(
+            decoded,
+            Some(StructValue::Struct(
+                "Mixed".to_string(),
+                vec![
+                    ("a".to_string(), StructValue::UInt(3)),
+                    ("b".to_string(), StructValue::Bool(true)),
+                    ("c".to_string(), StructValue::UInt(1)),
+                ],
+            ))
+        );
+    }
+
+    #[test]
+    fn decodes_enums() {
+        let registry = registry(&[(
+            "Mode",
+            "enum {off=0, on=1, auto=2} int8 mode;enum {a=1} uint8 other",
+        )]);
+        let decoded = decode(&registry, "struct:Mode", vec![2, 5]);
+        assert_eq!(
+            decoded,
+            Some(StructValue::Struct(
+                "Mode".to_string(),
+                vec![
+                    (
+                        "mode".to_string(),
+                        StructValue::Enum(Some("auto".to_string()), 2)
+                    ),
+                    ("other".to_string(), StructValue::Enum(None, 5)),
+                ],
+            ))
+        );
+    }
+
+    #[test]
+    fn decodes_arrays() {
+        let registry = registry(&[
+            ("Translation2d", "double x;double y"),
+            ("Named", "char name[4];int16 data[2]"),
+        ]);
+
+        let decoded = decode(
+            &registry,
+            "struct:Translation2d[]",
+            doubles(&[1.0, 2.0, 3.0, 4.0]),
+        );
+        let Some(StructValue::Array(items)) = decoded else {
+            panic!("expected an array, got {:?}", decoded);
+        };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].to_json(), json!({"x": 3.0, "y": 4.0}));
+
+        let decoded = decode(
+            &registry,
+            "struct:Named",
+            vec![b'a', b'b', 0, 0, 0xff, 0xff, 7, 0],
+        );
+        assert_eq!(
+            decoded.map(|v| v.to_json()),
+            Some(json!({"name": "ab", "data": [-1, 7]}))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_values() {
+        let registry = pose_registry();
+        assert_eq!(
+            decode(&registry, "struct:Pose2d", doubles(&[1.0, 2.0])),
+            None
+        );
+        assert_eq!(decode(&registry, "struct:Pose2d[]", vec![0; 30]), None);
+    }
+
+    #[test]
+    fn rejects_empty_struct_arrays() {
+        let registry = registry(&[("Empty", "")]);
+        assert_eq!(decode(&registry, "struct:Empty[]", vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn rejects_recursive_schemas() {
+        let registry = registry(&[("Node", "int8 value;Node next")]);
+        assert_eq!(decode(&registry, "struct:Node", vec![0; 64]), None);
+    }
+}
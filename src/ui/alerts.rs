@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem},
+};
+
+use crate::config::Widget;
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+use crate::value::NtValue;
+
+/// `.type` of the tables an Alert group publishes
+pub const ALERTS_TYPE: &str = "Alerts";
+
+/// Alert arrays by severity, most severe first
+const SEVERITIES: [(&str, &str, Color); 3] = [
+    ("errors", "✖ ", Color::Red),
+    ("warnings", "▲ ", Color::Yellow),
+    ("infos", "● ", Color::Cyan),
+];
+
+impl App {
+    fn alerts(&self, table: &str, severity: &str) -> &[String] {
+        match self.values.get(&format!("{}/{}", table, severity)) {
+            Some(NtValue::StringArray(alerts)) => alerts,
+            _ => &[],
+        }
+    }
+
+    /// Active errors across every alert group, whether or not it has a widget
+    pub fn alert_error_count(&self) -> usize {
+        self.values
+            .iter()
+            .filter(|(_, value)| matches!(value, NtValue::String(ty) if ty == ALERTS_TYPE))
+            .filter_map(|(topic, _)| topic.strip_suffix("/.type"))
+            .map(|table| self.alerts(table, "errors").len())
+            .sum()
+    }
+}
+
+/// Lists the group's active alerts, errors first
+pub fn render_alerts(f: &mut ratatui::Frame, app: &App, widget: &Widget, area: Rect, block: Block) {
+    let items: Vec<ListItem> = SEVERITIES
+        .iter()
+        .flat_map(|(severity, icon, color)| {
+            app.alerts(&widget.topic, severity)
+                .iter()
+                .map(move |alert| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(*icon, Style::default().fg(*color)),
+                        Span::styled(alert.clone(), Style::default().fg(*color)),
+                    ]))
+                })
+        })
+        .collect();
+
+    if items.is_empty() {
+        render_placeholder(f, area, block, "No alerts");
+        return;
+    }
+
+    // Any error turns the border red so it stands out even when the list is cut off
+    let block = if app.alerts(&widget.topic, "errors").is_empty() {
+        block
+    } else {
+        block.border_style(Style::default().fg(Color::Red))
+    };
+    f.render_widget(List::new(items).block(block), area);
+}
+
+/// Status bar text for the active error count
+pub fn error_count_line(count: usize) -> Line<'static> {
+    let count = if count > 0 {
+        count.to_string().red().bold()
+    } else {
+        count.to_string().dark_gray()
+    };
+    Line::from(vec!["Errors: ".bold(), count])
+}
@@ -1,62 +1,281 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
+use ratatui::layout::Rect;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast::Sender;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::{
-    config::{Config, GridPosition, Widget},
+    config::{Config, GridPosition, Widget, WidgetType},
+    nt::NtUpdate,
+    playback::{PlaybackControl, PlaybackStatus},
+    recorder::Recorder,
+    structs::StructRegistry,
+    ui::alerts::ALERTS_TYPE,
+    ui::browser::TopicBrowser,
+    ui::chooser::{CHOOSER_TYPE, ChooserPicker},
+    ui::command::{COMMAND_TYPE, SUBSYSTEM_TYPE},
+    ui::console::ConsoleView,
+    ui::export::ExportForm,
+    ui::field::FIELD2D_TYPE,
     ui::fuzzy::FuzzySearch,
-    ui::{ConnectionStatus, Window},
+    ui::graph::History,
+    ui::mechanism::MECHANISM2D_TYPE,
+    ui::mouse::MouseState,
+    ui::pid::{PID_TYPE, PidTuner},
+    ui::{ConnectionStatus, Options, Window},
+    value::{NtType, NtValue},
 };
 
 pub struct App {
-    pub values: HashMap<String, String>,
+    pub values: HashMap<String, NtValue>,
+    pub topic_types: HashMap<String, NtType>,
+    pub last_updated: HashMap<String, f64>,
     pub connection_status: ConnectionStatus,
     pub available_topics: HashSet<String>,
+    /// Topics that were unannounced and haven't come back yet
+    pub gone_topics: HashSet<String>,
     pub mode: Window,
     pub fuzzy_search: FuzzySearch,
     pub config: Config,
+    pub current_tab: usize,
+    /// Set after `g` so `gt`/`gT` can switch tabs
+    pub pending_g: bool,
+    pub tab_name_edit: String,
+    /// Whether the tab name input creates a new tab or renames the current one
+    pub tab_name_new: bool,
     pub paused: bool,
     pub selected_cell: Option<(usize, usize)>,
     pub label_edit: String,
+    pub value_edit: String,
+    pub value_edit_error: Option<String>,
+    pub value_edit_topic: String,
+    pub value_edit_return: Window,
+    /// Rows in the grid, including ones scrolled out of view
     pub max_rows: usize,
+    pub visible_rows: usize,
+    /// First grid row shown in the viewport
+    pub scroll_row: usize,
     pub last_activity: Instant,
     pub cursor_visible: bool,
     pub highlight_visible: bool,
     pub copy_message: Option<String>,
     pub copy_message_timestamp: Option<Instant>,
+    pub history: History,
+    pub start_time: Instant,
+    pub paused_at: Option<f64>,
+    pub sender: Sender<NtUpdate>,
+    /// Publishing is disabled from the command line
+    pub read_only: bool,
+    pub recorder: Option<Recorder>,
+    /// Updates for the active recording, read from their own NT receiver
+    pub recording_feed: Option<UnboundedReceiver<Result<NtUpdate, RecvError>>>,
+    pub export: ExportForm,
+    /// Latest player state when replaying a log instead of connecting live
+    pub playback: Option<PlaybackStatus>,
+    pub playback_control: Option<UnboundedSender<PlaybackControl>>,
+    pub record_dir: PathBuf,
+    pub console: ConsoleView,
+    pub browser: TopicBrowser,
+    /// Widget index and pending position while moving or resizing
+    pub move_preview: Option<(usize, GridPosition)>,
+    /// Screen areas of the visible grid cells from the last frame
+    pub grid_cells: Vec<Vec<Rect>>,
+    pub mouse: MouseState,
+    /// Schemas for decoding `struct:` topics
+    pub structs: StructRegistry,
+    pub chooser: ChooserPicker,
+    pub pid: PidTuner,
 }
 impl App {
-    pub fn new() -> App {
+    pub fn new(sender: Sender<NtUpdate>, config: Config, options: &Options) -> App {
         App {
             values: HashMap::new(),
+            topic_types: HashMap::new(),
+            last_updated: HashMap::new(),
             connection_status: ConnectionStatus::Disconnected,
             available_topics: HashSet::new(),
+            gone_topics: HashSet::new(),
             mode: Window::Main,
             fuzzy_search: FuzzySearch::new(),
-            config: Config::load().unwrap_or_else(|_| Config {
-                widgets: Vec::new(),
-            }),
+            config,
+            current_tab: 0,
+            pending_g: false,
+            tab_name_edit: String::new(),
+            tab_name_new: false,
             paused: false,
             selected_cell: None,
             label_edit: String::new(),
+            value_edit: String::new(),
+            value_edit_error: None,
+            value_edit_topic: String::new(),
+            value_edit_return: Window::CellConfig,
             max_rows: 8,
+            visible_rows: 8,
+            scroll_row: 0,
             last_activity: Instant::now(),
             highlight_visible: false,
             cursor_visible: false,
             copy_message: None,
             copy_message_timestamp: None,
+            history: History::new(),
+            start_time: Instant::now(),
+            paused_at: None,
+            sender,
+            read_only: options.read_only,
+            recorder: None,
+            recording_feed: None,
+            export: ExportForm::new(),
+            playback: None,
+            playback_control: options.playback_control.clone(),
+            record_dir: options.record_dir.clone(),
+            console: ConsoleView::new(),
+            browser: TopicBrowser::new(),
+            move_preview: None,
+            grid_cells: Vec::new(),
+            mouse: MouseState::new(),
+            structs: StructRegistry::new(),
+            chooser: ChooserPicker::new(),
+            pid: PidTuner::new(),
+        }
+    }
+
+    /// Decodes stored `struct:` values again once a new schema arrives
+    pub fn redecode_structs(&mut self) {
+        for value in self.values.values_mut() {
+            if matches!(value, NtValue::Other(..)) {
+                *value = self.structs.decode(value.clone());
+            }
+        }
+    }
+
+    /// Applies an update from NT, or one of our own publishes the server won't echo back
+    pub fn apply_update(&mut self, update: NtUpdate) {
+        match update {
+            NtUpdate::Announced(key, ty) => {
+                self.gone_topics.remove(&key);
+                self.topic_types.insert(key.clone(), ty);
+                self.connection_status = ConnectionStatus::Connected;
+                self.available_topics.insert(key);
+                if self.mode == Window::FuzzySearch {
+                    self.fuzzy_search.update_matches(&self.available_topics);
+                }
+            }
+            NtUpdate::Updated(key, value) => {
+                let k = key.clone();
+                if self.structs.observe(&key, &value) {
+                    self.redecode_structs();
+                }
+                let value = self.structs.decode(value);
+                // Only update values if not paused
+                if !self.paused {
+                    if let Some(v) = value.as_f64() {
+                        let now = self.now();
+                        self.history.record(&key, now, v);
+                    }
+                    self.topic_types.insert(key.clone(), value.nt_type());
+                    self.last_updated.insert(key.clone(), self.now());
+                    self.values.insert(key, value);
+                }
+                // Always update connection status and available topics
+                self.connection_status = ConnectionStatus::Connected;
+                self.gone_topics.remove(&k);
+                if self.available_topics.insert(k) && self.mode == Window::FuzzySearch {
+                    self.fuzzy_search.update_matches(&self.available_topics);
+                }
+            }
+            NtUpdate::Unannounced(key) => self.remove_topic(&key),
+            NtUpdate::ConnectionStatus(status) => {
+                self.connection_status = status;
+            }
+            NtUpdate::Playback(status) => self.playback = Some(status),
+            NtUpdate::Reset => self.reset_topics(),
+            NtUpdate::Publish(_, _) | NtUpdate::PublishResult(..) => {}
         }
     }
 
     pub fn add_widget(&mut self, widget: Widget) -> Result<(), Box<dyn std::error::Error>> {
-        self.config.add_widget(widget)?;
+        self.config.add_widget(self.current_tab, widget)?;
         Ok(())
     }
 
+    /// Widgets on the current tab
+    pub fn widgets(&self) -> &Vec<Widget> {
+        &self.config.tabs[self.current_tab].widgets
+    }
+
+    pub fn widgets_mut(&mut self) -> &mut Vec<Widget> {
+        &mut self.config.tabs[self.current_tab].widgets
+    }
+
+    /// The `.type` a WPILib sendable publishes under its table, e.g. "String Chooser"
+    pub fn sendable_type(&self, table: &str) -> Option<&str> {
+        self.sendable_string(table, ".type")
+    }
+
+    /// A string entry of a sendable, e.g. a chooser's `active` option
+    pub fn sendable_string(&self, table: &str, key: &str) -> Option<&str> {
+        match self.values.get(&format!("{}/{}", table, key)) {
+            Some(NtValue::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Values directly under a sendable's table by name, in name order.
+    /// Metadata such as `.type` and `.name` is left out.
+    pub fn sendable_entries(&self, table: &str) -> Vec<(&str, &NtValue)> {
+        let prefix = format!("{}/", table);
+        let mut entries: Vec<_> = self
+            .values
+            .iter()
+            .filter_map(|(topic, value)| {
+                let name = topic.strip_prefix(&prefix)?;
+                (!name.contains('/') && !name.starts_with('.')).then_some((name, value))
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+
+    /// Adds a widget for `topic` in the next free cell, picking an indicator for boolean
+    /// topics and a matching widget for sendable tables
+    pub fn add_widget_for_topic(&mut self, topic: &str) {
+        let widget_type = match (self.sendable_type(topic), self.topic_types.get(topic)) {
+            (Some(CHOOSER_TYPE), _) => WidgetType::Chooser,
+            (Some(FIELD2D_TYPE), _) => WidgetType::Field,
+            (Some(MECHANISM2D_TYPE), _) => WidgetType::Mechanism,
+            (Some(COMMAND_TYPE), _) => WidgetType::Command,
+            (Some(SUBSYSTEM_TYPE), _) => WidgetType::Subsystem,
+            (Some(PID_TYPE), _) => WidgetType::Pid,
+            (Some(ALERTS_TYPE), _) => WidgetType::Alerts,
+            (_, Some(NtType::Boolean)) => WidgetType::Boolean,
+            _ => WidgetType::Text,
+        };
+        let widget = Widget {
+            topic: topic.to_string(),
+            label: topic.to_string(),
+            widget_type,
+            position: self.find_next_grid_position(),
+            graph: None,
+            gauge: None,
+            boolean: None,
+            field: None,
+        };
+
+        let _ = self.add_widget(widget);
+    }
+
+    /// Number of grid columns from the config
+    pub fn columns(&self) -> usize {
+        self.config.grid.columns.max(1)
+    }
+
     pub fn find_next_grid_position(&self) -> GridPosition {
-        // Find first empty cell in the grid (5 columns, dynamic rows)
+        // Find first empty cell in the grid (configured columns, dynamic rows)
         for row in 0..self.max_rows {
-            for col in 0..5 {
+            for col in 0..self.columns() {
                 if !self.is_position_occupied(row, col) {
                     return GridPosition {
                         row,
@@ -78,16 +297,54 @@ impl App {
     }
 
     fn is_position_occupied(&self, row: usize, col: usize) -> bool {
-        self.config.widgets.iter().any(|w| {
-            row >= w.position.row
-                && row < w.position.row + w.position.row_span
-                && col >= w.position.col
-                && col < w.position.col + w.position.col_span
-        })
+        self.is_position_occupied_by_other(row, col, None)
+    }
+
+    /// Like `is_position_occupied`, but ignores the widget at index `ignore`
+    pub fn is_position_occupied_by_other(
+        &self,
+        row: usize,
+        col: usize,
+        ignore: Option<usize>,
+    ) -> bool {
+        self.widgets()
+            .iter()
+            .enumerate()
+            .any(|(i, w)| Some(i) != ignore && w.position.contains(row, col))
+    }
+
+    /// Index of the widget covering the selected cell on the current tab
+    pub fn selected_widget_index(&self) -> Option<usize> {
+        let (row, col) = self.selected_cell?;
+        self.widgets()
+            .iter()
+            .position(|w| w.position.contains(row, col))
+    }
+
+    pub fn remove_topic(&mut self, topic: &str) {
+        self.available_topics.remove(topic);
+        self.values.remove(topic);
+        self.topic_types.remove(topic);
+        self.last_updated.remove(topic);
+        self.gone_topics.insert(topic.to_string());
+        if self.mode == Window::FuzzySearch {
+            self.fuzzy_search.update_matches(&self.available_topics);
+        }
     }
 
     pub fn toggle_pause(&mut self) {
         self.paused = !self.paused;
+        self.paused_at = if self.paused { Some(self.now()) } else { None };
+    }
+
+    /// Seconds since the app started, frozen while paused so graphs stop scrolling.
+    /// When replaying, this is the position in the log instead.
+    pub fn now(&self) -> f64 {
+        if let Some(playback) = &self.playback {
+            return playback.position;
+        }
+        self.paused_at
+            .unwrap_or_else(|| self.start_time.elapsed().as_secs_f64())
     }
 
     pub fn move_selection(&mut self, row_delta: isize, col_delta: isize) {
@@ -97,7 +354,9 @@ impl App {
         let new_row = (row as isize + row_delta)
             .max(0)
             .min((self.max_rows - 1) as isize) as usize;
-        let new_col = (col as isize + col_delta).max(0).min(4) as usize;
+        let new_col = (col as isize + col_delta)
+            .max(0)
+            .min(self.columns() as isize - 1) as usize;
 
         self.selected_cell = Some((new_row, new_col));
         self.update_activity();
@@ -114,24 +373,21 @@ impl App {
     }
 
     pub fn get_widget_at_selected_cell(&self) -> Option<&Widget> {
-        if let Some((row, col)) = self.selected_cell {
-            self.config
-                .widgets
-                .iter()
-                .find(|w| w.position.row == row && w.position.col == col)
-        } else {
-            None
-        }
+        let index = self.selected_widget_index()?;
+        self.widgets().get(index)
     }
 
     pub fn get_widget_at_selected_cell_mut(&mut self) -> Option<&mut Widget> {
-        if let Some((row, col)) = self.selected_cell {
-            self.config
-                .widgets
-                .iter_mut()
-                .find(|w| w.position.row == row && w.position.col == col)
-        } else {
-            None
+        let index = self.selected_widget_index()?;
+        self.widgets_mut().get_mut(index)
+    }
+
+    pub fn cycle_widget_type(&mut self) {
+        if let Some(widget) = self.get_widget_at_selected_cell_mut() {
+            widget.widget_type = widget.widget_type.next();
+            self.config.save().unwrap_or_else(|e| {
+                log::error!("Failed to save config: {}", e);
+            });
         }
     }
 
@@ -158,6 +414,79 @@ impl App {
         self.exit_label_edit();
     }
 
+    pub fn enter_value_edit(&mut self) {
+        let Some(widget) = self.get_widget_at_selected_cell() else {
+            return;
+        };
+        let topic = widget.topic.clone();
+        match widget.widget_type {
+            WidgetType::Chooser => self.enter_chooser(topic),
+            WidgetType::Command => self.toggle_command(&topic),
+            WidgetType::Pid => self.enter_pid_tune(topic),
+            _ => self.enter_value_edit_for(topic),
+        }
+    }
+
+    /// Opens the value input for `topic`, returning to the current window afterwards
+    pub fn enter_value_edit_for(&mut self, topic: String) {
+        if self.read_only {
+            self.set_copy_message("Read-only mode, publishing is disabled".to_string());
+            return;
+        }
+
+        // Start from the current value so small tweaks are quick
+        self.value_edit = self
+            .values
+            .get(&topic)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        self.value_edit_topic = topic;
+        self.value_edit_error = None;
+        self.value_edit_return = self.mode.clone();
+        self.mode = Window::ValueEdit;
+    }
+
+    pub fn exit_value_edit(&mut self) {
+        self.value_edit_error = None;
+        self.mode = self.value_edit_return.clone();
+    }
+
+    pub fn publish_value(&mut self) {
+        let topic = self.value_edit_topic.clone();
+        let Some(ty) = self.topic_types.get(&topic) else {
+            self.value_edit_error = Some("Topic type is unknown, can't publish yet".to_string());
+            return;
+        };
+
+        match NtValue::parse(ty, &self.value_edit) {
+            Ok(value) => {
+                let message = format!("Published {} to {}", value, topic);
+                if self.publish(topic, value) {
+                    self.set_copy_message(message);
+                }
+                self.exit_value_edit();
+            }
+            Err(e) => self.value_edit_error = Some(e),
+        }
+    }
+
+    /// Publishes a value and applies it here too, since the server doesn't echo our own
+    /// writes. It's shown even while paused, so the next edit starts from what was sent.
+    pub fn publish(&mut self, topic: String, value: NtValue) -> bool {
+        if let Err(e) = self
+            .sender
+            .send(NtUpdate::Publish(topic.clone(), value.to_rmpv()))
+        {
+            log::error!("Failed to queue publish for {}: {}", topic, e);
+            return false;
+        }
+        let update = NtUpdate::Updated(topic.clone(), value.clone());
+        self.record_update(&update);
+        self.apply_update(update);
+        self.values.insert(topic, value);
+        true
+    }
+
     pub fn update_activity(&mut self) {
         self.last_activity = Instant::now();
         self.highlight_visible = true;
@@ -185,34 +514,22 @@ impl App {
     }
 
     pub fn copy_selected_value(&mut self) {
-        if let Some((row, col)) = self.selected_cell {
-            if let Some(widget) = self
-                .config
-                .widgets
-                .iter()
-                .find(|w| w.position.row == row && w.position.col == col)
-            {
-                if let Some(value) = self.values.get(&widget.topic) {
-                    if let Ok(mut ctx) = ClipboardContext::new() {
-                        if ctx.set_contents(value.to_owned()).is_ok() {
-                            self.set_copy_message(format!("Copied: {}", value));
-                        }
+        if let Some(widget) = self.get_widget_at_selected_cell() {
+            if let Some(value) = self.values.get(&widget.topic).map(|v| v.to_string()) {
+                if let Ok(mut ctx) = ClipboardContext::new() {
+                    if ctx.set_contents(value.clone()).is_ok() {
+                        self.set_copy_message(format!("Copied: {}", value));
                     }
                 }
             }
         }
     }
     pub fn delete_selected_widget(&mut self) {
-        if let Some((row, col)) = self.selected_cell {
+        if self.selected_cell.is_some() {
             // Find the index of the widget to delete
-            if let Some(index) = self
-                .config
-                .widgets
-                .iter()
-                .position(|w| w.position.row == row && w.position.col == col)
-            {
+            if let Some(index) = self.selected_widget_index() {
                 // Remove the widget
-                self.config.widgets.remove(index);
+                self.widgets_mut().remove(index);
 
                 // Save the updated config
                 self.config.save().unwrap_or_else(|e| {
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Paragraph},
+};
+
+use crate::config::{BooleanConfig, Widget};
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+
+pub fn render_boolean(
+    f: &mut ratatui::Frame,
+    app: &App,
+    widget: &Widget,
+    area: Rect,
+    block: Block,
+) {
+    let default_config = BooleanConfig::default();
+    let config = widget.boolean.as_ref().unwrap_or(&default_config);
+
+    let Some(value) = app.values.get(&widget.topic).and_then(|v| v.as_bool()) else {
+        render_placeholder(f, area, block, "No value");
+        return;
+    };
+
+    let (color, label) = if value {
+        (
+            &config.true_color,
+            config.true_label.as_deref().unwrap_or("true"),
+        )
+    } else {
+        (
+            &config.false_color,
+            config.false_label.as_deref().unwrap_or("false"),
+        )
+    };
+    let color = Color::from_str(color).unwrap_or(if value { Color::Green } else { Color::Red });
+
+    // Fill the whole cell, border included, so the state reads from a distance
+    let fill = block.style(Style::default().bg(color));
+    let inner = fill.inner(area);
+    f.render_widget(fill, area);
+
+    // Vertically center the label inside the filled cell
+    let [label_area] = Layout::vertical([Constraint::Length(1)])
+        .flex(Flex::Center)
+        .areas(inner);
+    let text = Paragraph::new(label.to_string()).centered().style(
+        Style::default()
+            .fg(Color::Black)
+            .bg(color)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(text, label_area);
+}
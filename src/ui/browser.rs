@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, HashSet};
+
+use clipboard::{ClipboardContext, ClipboardProvider};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use crate::ui::Window;
+use crate::ui::app::App;
+
+/// A node in the topic tree; `topic` is set when the path itself is a topic
+#[derive(Default)]
+struct Node {
+    children: BTreeMap<String, Node>,
+    topic: Option<String>,
+}
+
+/// One visible line of the flattened tree
+pub struct BrowserRow {
+    pub path: String,
+    pub name: String,
+    pub depth: usize,
+    pub topic: Option<String>,
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+pub struct TopicBrowser {
+    /// Paths of the expanded tree nodes
+    pub expanded: HashSet<String>,
+    pub selected: usize,
+    pub table_state: TableState,
+}
+
+impl TopicBrowser {
+    pub fn new() -> Self {
+        Self {
+            expanded: HashSet::new(),
+            selected: 0,
+            table_state: TableState::default(),
+        }
+    }
+}
+
+fn build_tree(topics: &HashSet<String>) -> Node {
+    let mut root = Node::default();
+    for topic in topics {
+        let mut node = &mut root;
+        for segment in topic.split('/').filter(|s| !s.is_empty()) {
+            node = node.children.entry(segment.to_string()).or_default();
+        }
+        node.topic = Some(topic.clone());
+    }
+    root
+}
+
+fn flatten(
+    node: &Node,
+    path: &str,
+    depth: usize,
+    expanded: &HashSet<String>,
+    rows: &mut Vec<BrowserRow>,
+) {
+    for (name, child) in &node.children {
+        let child_path = format!("{}/{}", path, name);
+        let is_expanded = expanded.contains(&child_path);
+        rows.push(BrowserRow {
+            path: child_path.clone(),
+            name: name.clone(),
+            depth,
+            topic: child.topic.clone(),
+            has_children: !child.children.is_empty(),
+            expanded: is_expanded,
+        });
+        if is_expanded {
+            flatten(child, &child_path, depth + 1, expanded, rows);
+        }
+    }
+}
+
+impl App {
+    pub fn enter_topic_browser(&mut self) {
+        self.mode = Window::TopicBrowser;
+    }
+
+    pub fn exit_topic_browser(&mut self) {
+        self.mode = Window::Main;
+    }
+
+    pub fn browser_rows(&self) -> Vec<BrowserRow> {
+        let mut rows = Vec::new();
+        flatten(
+            &build_tree(&self.available_topics),
+            "",
+            0,
+            &self.browser.expanded,
+            &mut rows,
+        );
+        rows
+    }
+
+    fn selected_browser_row(&self) -> Option<BrowserRow> {
+        self.browser_rows().into_iter().nth(self.browser.selected)
+    }
+
+    pub fn browser_move(&mut self, delta: isize) {
+        let len = self.browser_rows().len();
+        if len == 0 {
+            return;
+        }
+        self.browser.selected =
+            (self.browser.selected as isize + delta).clamp(0, len as isize - 1) as usize;
+    }
+
+    pub fn browser_expand(&mut self) {
+        if let Some(row) = self.selected_browser_row()
+            && row.has_children
+        {
+            self.browser.expanded.insert(row.path);
+        }
+    }
+
+    /// Collapses the selected node, or jumps to its parent if it's already collapsed
+    pub fn browser_collapse(&mut self) {
+        let Some(row) = self.selected_browser_row() else {
+            return;
+        };
+        if row.expanded {
+            self.browser.expanded.remove(&row.path);
+            return;
+        }
+        if let Some((parent, _)) = row.path.rsplit_once('/')
+            && let Some(index) = self.browser_rows().iter().position(|r| r.path == parent)
+        {
+            self.browser.selected = index;
+        }
+    }
+
+    pub fn browser_toggle(&mut self) {
+        match self.selected_browser_row() {
+            Some(row) if row.expanded => self.browser_collapse(),
+            Some(_) => self.browser_expand(),
+            None => {}
+        }
+    }
+
+    pub fn browser_add_widget(&mut self) {
+        let Some(row) = self.selected_browser_row() else {
+            return;
+        };
+        // Sendable tables aren't topics themselves, but have widgets of their own
+        let topic = match row.topic {
+            Some(topic) => topic,
+            None if self.sendable_type(&row.path).is_some() => row.path,
+            None => return,
+        };
+        self.add_widget_for_topic(&topic);
+        self.set_copy_message(format!("Added widget for {}", topic));
+    }
+
+    pub fn browser_copy_path(&mut self) {
+        if let Some(row) = self.selected_browser_row() {
+            let path = row.topic.unwrap_or(row.path);
+            if let Ok(mut ctx) = ClipboardContext::new()
+                && ctx.set_contents(path.clone()).is_ok()
+            {
+                self.set_copy_message(format!("Copied: {}", path));
+            }
+        }
+    }
+
+    pub fn browser_publish(&mut self) {
+        if let Some(topic) = self.selected_browser_row().and_then(|r| r.topic) {
+            self.enter_value_edit_for(topic);
+        }
+    }
+}
+
+fn format_age(age: f64) -> String {
+    if age < 60.0 {
+        format!("{:.1}s ago", age)
+    } else if age < 3600.0 {
+        format!("{:.0}m ago", age / 60.0)
+    } else {
+        format!("{:.0}h ago", age / 3600.0)
+    }
+}
+
+pub fn render_topic_browser(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
+    f.render_widget(Clear, area);
+
+    let rows = app.browser_rows();
+    app.browser.selected = app.browser.selected.min(rows.len().saturating_sub(1));
+    app.browser.table_state.select(if rows.is_empty() {
+        None
+    } else {
+        Some(app.browser.selected)
+    });
+
+    let now = app.now();
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .map(|row| {
+            let marker = if !row.has_children {
+                "  "
+            } else if row.expanded {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            let name = Line::from(vec![
+                Span::raw("  ".repeat(row.depth)),
+                Span::styled(marker, Style::default().fg(Color::DarkGray)),
+                if row.topic.is_some() {
+                    Span::styled(row.name.clone(), Style::default().fg(Color::Cyan))
+                } else {
+                    Span::styled(row.name.clone(), Style::default().bold())
+                },
+            ]);
+
+            let (value, ty, age) = match &row.topic {
+                Some(topic) => (
+                    app.values
+                        .get(topic)
+                        .map(|v| v.to_string())
+                        .unwrap_or_default(),
+                    app.topic_types
+                        .get(topic)
+                        .map(|t| t.to_string())
+                        .unwrap_or_default(),
+                    app.last_updated
+                        .get(topic)
+                        .map(|t| format_age(now - t))
+                        .unwrap_or_default(),
+                ),
+                None => Default::default(),
+            };
+
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(value).style(Style::default().fg(Color::LightYellow)),
+                Cell::from(ty).style(Style::default().fg(Color::Magenta)),
+                Cell::from(age).style(Style::default().dim()),
+            ])
+        })
+        .collect();
+
+    let help = Line::from(vec![
+        " [".dim(),
+        "jk".blue().bold(),
+        "] ".dim(),
+        "Move".reset(),
+        "  [".dim(),
+        "hl".blue().bold(),
+        "] ".dim(),
+        "Collapse/Expand".reset(),
+        "  [".dim(),
+        "a".green().bold(),
+        "] ".dim(),
+        "Add Widget".reset(),
+        "  [".dim(),
+        "y".magenta().bold(),
+        "] ".dim(),
+        "Copy Path".reset(),
+        "  [".dim(),
+        "p".magenta().bold(),
+        "] ".dim(),
+        "Publish".reset(),
+        "  [".dim(),
+        "Esc".red().bold(),
+        "] ".dim(),
+        "Back ".reset(),
+    ]);
+
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(35),
+            Constraint::Percentage(12),
+            Constraint::Percentage(13),
+        ],
+    )
+    .header(
+        Row::new(vec!["Topic", "Value", "Type", "Updated"])
+            .style(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
+    )
+    .row_highlight_style(
+        Style::default()
+            .bg(Color::Black)
+            .fg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(
+        Block::default()
+            .title(format!("Topics ({})", app.available_topics.len()))
+            .title_bottom(help)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+
+    f.render_stateful_widget(table, area, &mut app.browser.table_state);
+}
@@ -0,0 +1,233 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+};
+use rmpv::Value;
+
+use crate::config::Widget;
+use crate::nt::NtUpdate;
+use crate::ui::Window;
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+use crate::value::NtValue;
+
+/// `.type` of the tables SendableChooser publishes
+pub const CHOOSER_TYPE: &str = "String Chooser";
+
+/// The chooser popup, picking an option for the chooser under `table`
+pub struct ChooserPicker {
+    pub table: String,
+    pub list_state: ListState,
+    pub return_to: Window,
+}
+
+impl ChooserPicker {
+    pub fn new() -> Self {
+        Self {
+            table: String::new(),
+            list_state: ListState::default(),
+            return_to: Window::Main,
+        }
+    }
+}
+
+impl App {
+    pub fn chooser_options(&self, table: &str) -> &[String] {
+        match self.values.get(&format!("{}/options", table)) {
+            Some(NtValue::StringArray(options)) => options,
+            _ => &[],
+        }
+    }
+
+    pub fn enter_chooser(&mut self, table: String) {
+        if self.read_only {
+            self.set_copy_message("Read-only mode, publishing is disabled".to_string());
+            return;
+        }
+        let options = self.chooser_options(&table);
+        if options.is_empty() {
+            self.set_copy_message("Chooser has no options yet".to_string());
+            return;
+        }
+
+        // Start on whatever the robot is running, so Enter right away is a no-op
+        let current = self
+            .sendable_string(&table, "active")
+            .or(self.sendable_string(&table, "default"));
+        let index = options
+            .iter()
+            .position(|o| Some(o.as_str()) == current)
+            .unwrap_or(0);
+
+        self.chooser.list_state.select(Some(index));
+        self.chooser.table = table;
+        self.chooser.return_to = self.mode.clone();
+        self.mode = Window::Chooser;
+    }
+
+    pub fn exit_chooser(&mut self) {
+        self.mode = self.chooser.return_to.clone();
+    }
+
+    pub fn chooser_move(&mut self, delta: isize) {
+        let len = self.chooser_options(&self.chooser.table).len();
+        if len == 0 {
+            return;
+        }
+        let index = self.chooser.list_state.selected().unwrap_or(0) as isize + delta;
+        self.chooser
+            .list_state
+            .select(Some(index.rem_euclid(len as isize) as usize));
+    }
+
+    /// Publishes the highlighted option to the chooser's `selected` topic
+    pub fn publish_choice(&mut self) {
+        let table = self.chooser.table.clone();
+        let index = self.chooser.list_state.selected().unwrap_or(0);
+        let Some(option) = self.chooser_options(&table).get(index).cloned() else {
+            return;
+        };
+
+        let topic = format!("{}/selected", table);
+        match self.sender.send(NtUpdate::Publish(
+            topic.clone(),
+            Value::from(option.as_str()),
+        )) {
+            Ok(_) => self.set_copy_message(format!("Selected {}", option)),
+            Err(e) => log::error!("Failed to queue publish for {}: {}", topic, e),
+        }
+        self.exit_chooser();
+    }
+}
+
+/// Lists the options in the cell, marking the one the robot reports as active
+pub fn render_chooser(
+    f: &mut ratatui::Frame,
+    app: &App,
+    widget: &Widget,
+    area: Rect,
+    block: Block,
+) {
+    let options = app.chooser_options(&widget.topic);
+    if options.is_empty() {
+        render_placeholder(f, area, block, "No options");
+        return;
+    }
+
+    let active = app.sendable_string(&widget.topic, "active");
+    let selected = app.sendable_string(&widget.topic, "selected");
+    let default = app.sendable_string(&widget.topic, "default");
+
+    let items: Vec<ListItem> = options
+        .iter()
+        .map(|option| {
+            let name = Some(option.as_str());
+            let mut spans = if name == active {
+                vec!["● ".green(), Span::from(option.as_str()).green().bold()]
+            } else if name == selected {
+                // Picked, but the robot hasn't switched to it yet
+                vec!["○ ".yellow(), Span::from(option.as_str()).yellow()]
+            } else {
+                vec!["  ".into(), Span::from(option.as_str())]
+            };
+            if name == default {
+                spans.push(" (default)".dim());
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    // Scroll so the active option stays in view in short cells
+    let mut state =
+        ListState::default().with_selected(options.iter().position(|o| Some(o.as_str()) == active));
+    let list = List::new(items).block(block);
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+pub fn render_chooser_picker(f: &mut ratatui::Frame, app: &mut App, size: Rect) {
+    let options = app.chooser_options(&app.chooser.table).to_vec();
+
+    let popup_width = (size.width / 2).max(50);
+    // Room for every option plus borders and the controls box, within the screen
+    let popup_height = (options.len() as u16 + 5).min(size.height.saturating_sub(4));
+
+    let popup_x = (size.width - popup_width) / 2;
+    let popup_y = (size.height - popup_height) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Create a clear background for the popup
+    f.render_widget(Clear, popup_area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Options
+            Constraint::Length(3), // Controls
+        ])
+        .split(popup_area);
+
+    let active = app.sendable_string(&app.chooser.table, "active");
+    let items: Vec<ListItem> = options
+        .iter()
+        .map(|option| {
+            if Some(option.as_str()) == active {
+                ListItem::new(Line::from(vec![option.clone().into(), " (active)".green()]))
+            } else {
+                ListItem::new(option.clone())
+            }
+        })
+        .collect();
+
+    let name = app
+        .chooser
+        .table
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Choose {}", name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::horizontal(1)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::Black)
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let help_text = vec![Line::from(vec![
+        "[".dim(),
+        "↑↓".blue().bold(),
+        "] ".dim(),
+        "Move".reset(),
+        "   [".dim(),
+        "Enter".green().bold(),
+        "] ".dim(),
+        "Select".reset(),
+        "   [".dim(),
+        "Esc".red().bold(),
+        "] ".dim(),
+        "Cancel".reset(),
+    ])];
+
+    let controls_box = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .title("Controls")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::new(1, 0, 0, 0)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_stateful_widget(list, layout[0], &mut app.chooser.list_state);
+    f.render_widget(controls_box, layout[1]);
+}
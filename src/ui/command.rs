@@ -0,0 +1,129 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+use rmpv::Value;
+
+use crate::config::Widget;
+use crate::nt::NtUpdate;
+use crate::ui::app::App;
+use crate::value::NtValue;
+
+/// `.type` of the tables commands and subsystems publish
+pub const COMMAND_TYPE: &str = "Command";
+pub const SUBSYSTEM_TYPE: &str = "Subsystem";
+
+impl App {
+    fn command_running(&self, table: &str) -> bool {
+        self.values
+            .get(&format!("{}/running", table))
+            .and_then(NtValue::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Name a command reports, falling back to the last part of its table
+    fn command_name<'a>(&'a self, table: &'a str) -> &'a str {
+        self.sendable_string(table, ".name")
+            .unwrap_or_else(|| table.rsplit('/').next().unwrap_or(table))
+    }
+
+    /// Schedules the command under `table`, or cancels it if it's running
+    pub fn toggle_command(&mut self, table: &str) {
+        if self.read_only {
+            self.set_copy_message("Read-only mode, publishing is disabled".to_string());
+            return;
+        }
+
+        let running = self.command_running(table);
+        let topic = format!("{}/running", table);
+        match self
+            .sender
+            .send(NtUpdate::Publish(topic.clone(), Value::from(!running)))
+        {
+            Ok(_) => {
+                let action = if running { "Cancelled" } else { "Scheduled" };
+                let message = format!("{} {}", action, self.command_name(table));
+                self.set_copy_message(message);
+            }
+            Err(e) => log::error!("Failed to queue publish for {}: {}", topic, e),
+        }
+    }
+}
+
+/// A button filled green while the command runs
+pub fn render_command(
+    f: &mut ratatui::Frame,
+    app: &App,
+    widget: &Widget,
+    area: Rect,
+    block: Block,
+) {
+    let running = app.command_running(&widget.topic);
+    let (color, state) = if running {
+        (Color::Green, "Running")
+    } else {
+        (Color::DarkGray, "Idle")
+    };
+
+    let fill = block.style(Style::default().bg(color));
+    let inner = fill.inner(area);
+    f.render_widget(fill, area);
+
+    let lines = vec![
+        Line::from(app.command_name(&widget.topic).to_string()).bold(),
+        Line::from(state),
+    ];
+    let [text_area] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(Flex::Center)
+        .areas(inner);
+    let text = Paragraph::new(lines)
+        .centered()
+        .style(Style::default().fg(Color::Black).bg(color));
+    f.render_widget(text, text_area);
+}
+
+/// The subsystem's current command, and the default it falls back to
+pub fn render_subsystem(
+    f: &mut ratatui::Frame,
+    app: &App,
+    widget: &Widget,
+    area: Rect,
+    block: Block,
+) {
+    let current = app
+        .sendable_string(&widget.topic, ".command")
+        .filter(|_| has(app, &widget.topic, ".hasCommand"));
+    let default = app
+        .sendable_string(&widget.topic, ".default")
+        .filter(|_| has(app, &widget.topic, ".hasDefault"));
+
+    let lines = vec![
+        Line::from(vec![
+            "Current: ".bold(),
+            match current {
+                Some(name) => name.to_string().green().add_modifier(Modifier::BOLD),
+                None => "none".dark_gray(),
+            },
+        ]),
+        Line::from(vec![
+            "Default: ".bold(),
+            match default {
+                Some(name) => name.to_string().reset(),
+                None => "none".dark_gray(),
+            },
+        ]),
+    ];
+    let text = Paragraph::new(lines).block(block).centered();
+    f.render_widget(text, area);
+}
+
+// Subsystems leave their command names in place and flip these flags instead.
+// Older robot code doesn't publish the flags at all, so only an explicit false counts.
+fn has(app: &App, table: &str, flag: &str) -> bool {
+    app.values
+        .get(&format!("{}/{}", table, flag))
+        .and_then(NtValue::as_bool)
+        .unwrap_or(true)
+}
@@ -0,0 +1,174 @@
+use log::{Level, LevelFilter};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::console::{self, ConsoleEntry};
+use crate::ui::Window;
+use crate::ui::app::App;
+
+pub struct ConsoleView {
+    /// Lines scrolled up from the newest entry; 0 follows new output
+    pub scroll: usize,
+    pub level: LevelFilter,
+    pub search: String,
+    pub searching: bool,
+}
+
+impl ConsoleView {
+    pub fn new() -> Self {
+        Self {
+            scroll: 0,
+            level: LevelFilter::Info,
+            search: String::new(),
+            searching: false,
+        }
+    }
+
+    fn matches(&self, entry: &ConsoleEntry) -> bool {
+        if entry.level > self.level {
+            return false;
+        }
+        if self.search.is_empty() {
+            return true;
+        }
+        let search = self.search.to_lowercase();
+        entry.message.to_lowercase().contains(&search)
+            || entry.target.to_lowercase().contains(&search)
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = (self.scroll as isize + delta).max(0) as usize;
+    }
+
+    pub fn set_level(&mut self, level: LevelFilter) {
+        self.level = level;
+        self.scroll = 0;
+    }
+}
+
+impl App {
+    pub fn enter_console(&mut self) {
+        self.mode = Window::Console;
+    }
+
+    pub fn exit_console(&mut self) {
+        self.console.searching = false;
+        self.mode = Window::Main;
+    }
+
+    pub fn clear_console(&mut self) {
+        console::clear();
+        self.console.scroll = 0;
+    }
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::Error => Color::Red,
+        Level::Warn => Color::Yellow,
+        Level::Info => Color::Green,
+        Level::Debug => Color::Blue,
+        Level::Trace => Color::DarkGray,
+    }
+}
+
+pub fn render_console(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
+    f.render_widget(Clear, area);
+
+    let view = &mut app.console;
+    let entries = console::entries(|e| view.matches(e));
+
+    // Two rows of border plus one for the search line
+    let height = area.height.saturating_sub(3) as usize;
+    view.scroll = view.scroll.min(entries.len().saturating_sub(height));
+    let end = entries.len() - view.scroll;
+    let start = end.saturating_sub(height);
+
+    let mut lines: Vec<Line> = entries[start..end]
+        .iter()
+        .map(|entry| {
+            Line::from(vec![
+                Span::styled(format!("[{:>9.3}] ", entry.time), Style::default().dim()),
+                Span::styled(
+                    format!("{:<5} ", entry.level),
+                    Style::default().fg(level_color(entry.level)).bold(),
+                ),
+                Span::styled(
+                    format!("{} ", entry.target),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(entry.message.clone()),
+            ])
+        })
+        .collect();
+
+    // Pad so the search line always sits at the bottom
+    while lines.len() < height {
+        lines.insert(0, Line::from(""));
+    }
+
+    let search_line = if view.searching {
+        Line::from(vec![
+            "/".yellow().bold(),
+            view.search.clone().reset(),
+            if app.cursor_visible { "_" } else { " " }.reset(),
+        ])
+    } else if !view.search.is_empty() {
+        Line::from(vec!["Filter: ".dim(), view.search.clone().yellow()])
+    } else {
+        Line::from("")
+    };
+    lines.push(search_line);
+
+    let title = format!(
+        "Console ({} lines, level: {}{})",
+        entries.len(),
+        view.level,
+        if view.scroll > 0 {
+            format!(", {} more below", view.scroll)
+        } else {
+            String::new()
+        }
+    );
+
+    let help = Line::from(vec![
+        " [".dim(),
+        "jk".blue().bold(),
+        "] ".dim(),
+        "Scroll".reset(),
+        "  [".dim(),
+        "gG".blue().bold(),
+        "] ".dim(),
+        "Top/Bottom".reset(),
+        "  [".dim(),
+        "1-5".green().bold(),
+        "] ".dim(),
+        "Level".reset(),
+        "  [".dim(),
+        "/".yellow().bold(),
+        "] ".dim(),
+        "Search".reset(),
+        "  [".dim(),
+        "C".magenta().bold(),
+        "] ".dim(),
+        "Clear".reset(),
+        "  [".dim(),
+        "Esc".red().bold(),
+        "] ".dim(),
+        "Back ".reset(),
+    ]);
+
+    let console = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .title_bottom(help)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .padding(Padding::horizontal(1)),
+    );
+    f.render_widget(console, area);
+}
@@ -10,7 +10,7 @@ use ratatui::{
 pub fn render_cell_config(f: &mut ratatui::Frame, app: &App, size: Rect) {
     // Calculate popup dimensions - half of screen width/height with minimums
     let popup_width = (size.width / 2).max(50);
-    let popup_height = 12; // Fixed height with room for two boxes and padding
+    let popup_height = 15; // Fixed height with room for two boxes and padding
 
     let popup_x = (size.width - popup_width) / 2;
     let popup_y = (size.height - popup_height) / 2;
@@ -24,23 +24,36 @@ pub fn render_cell_config(f: &mut ratatui::Frame, app: &App, size: Rect) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5), // Widget info box (2 rows)
-            Constraint::Length(7), // Controls box
+            Constraint::Length(5),  // Widget info box (3 rows)
+            Constraint::Length(10), // Controls box
         ])
         .margin(0)
         .split(popup_area);
 
     // Get the widget at the selected cell
-    let (topic, label) = if let Some(widget) = app.get_widget_at_selected_cell() {
-        (widget.topic.clone(), widget.label.clone())
+    let (topic, label, widget_type) = if let Some(widget) = app.get_widget_at_selected_cell() {
+        (
+            widget.topic.clone(),
+            widget.label.clone(),
+            format!("{:?}", widget.widget_type),
+        )
     } else {
-        ("No widget selected".to_string(), "".to_string())
+        (
+            "No widget selected".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )
     };
+    let topic_type = app
+        .topic_types
+        .get(&topic)
+        .map_or("unknown".to_string(), |t| t.to_string());
 
-    // Create info box with two rows
+    // Create info box with three rows
     let info_text = vec![
         Line::from(vec!["Label: ".bold(), label.reset()]),
         Line::from(vec!["Topic: ".bold(), topic.reset()]),
+        Line::from(vec!["Type:  ".bold(), topic_type.reset()]),
     ];
 
     let info_box = Paragraph::new(info_text)
@@ -67,6 +80,25 @@ pub fn render_cell_config(f: &mut ratatui::Frame, app: &App, size: Rect) {
             "] ".dim(),
             "Edit Label".reset(),
         ]),
+        Line::from(vec![
+            "[".dim(),
+            "t".cyan().bold(),
+            "] ".dim(),
+            "Widget Type: ".reset(),
+            widget_type.cyan(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "p".magenta().bold(),
+            "] ".dim(),
+            "Set Value".reset(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "m".blue().bold(),
+            "] ".dim(),
+            "Move/Resize".reset(),
+        ]),
         Line::from(vec![
             "[".dim(),
             "D".red().bold(),
@@ -174,3 +206,90 @@ pub fn render_label_edit(f: &mut ratatui::Frame, app: &App, size: Rect) {
     f.render_widget(input_box, layout[0]);
     f.render_widget(controls_box, layout[1]);
 }
+
+pub fn render_value_edit(f: &mut ratatui::Frame, app: &App, size: Rect) {
+    let popup_width = (size.width / 2).max(50);
+    let popup_height = 11; // Input box, error line and controls
+
+    let popup_x = (size.width - popup_width) / 2;
+    let popup_y = (size.height - popup_height) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Create a clear background for the popup
+    f.render_widget(Clear, popup_area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input box
+            Constraint::Length(1), // Validation error
+            Constraint::Length(6), // Controls
+        ])
+        .margin(0)
+        .split(popup_area);
+
+    // Show the announced type so it's clear how the input will be parsed
+    let topic_type = app
+        .topic_types
+        .get(&app.value_edit_topic)
+        .map_or("unknown".to_string(), |t| t.to_string());
+
+    let input_text = format!(
+        "{}{}",
+        app.value_edit,
+        if app.cursor_visible { "_" } else { " " }
+    );
+
+    let input_box = Paragraph::new(input_text)
+        .block(
+            Block::default()
+                .title(format!("Set Value ({})", topic_type))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if app.value_edit_error.is_some() {
+                    Color::Red
+                } else {
+                    Color::Blue
+                }))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Left);
+
+    let error_line = Paragraph::new(app.value_edit_error.clone().unwrap_or_default().red())
+        .block(Block::default().padding(Padding::horizontal(2)));
+
+    let help_text = vec![
+        Line::from(vec![
+            "[".dim(),
+            "Enter".green().bold(),
+            "] ".dim(),
+            "Publish".reset(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "Ctrl+D".yellow().bold(),
+            "] ".dim(),
+            "Clear".reset(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "Esc".red().bold(),
+            "] ".dim(),
+            "Cancel".reset(),
+        ]),
+    ];
+
+    let controls_box = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .title("Controls")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::new(1, 0, 0, 0)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(input_box, layout[0]);
+    f.render_widget(error_line, layout[1]);
+    f.render_widget(controls_box, layout[2]);
+}
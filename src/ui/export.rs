@@ -0,0 +1,285 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::export::{self, Format, Samples};
+use crate::recorder;
+use crate::ui::Window;
+use crate::ui::app::App;
+use crate::value::{NtType, NtValue};
+
+/// How far back the export reaches into the graph history, in seconds
+const RANGES: [f64; 5] = [10.0, 30.0, 60.0, 120.0, 300.0];
+
+const FIELD_TOPICS: usize = 0;
+const FIELD_PREFIX: usize = 1;
+const FIELD_RANGE: usize = 2;
+const FIELD_FORMAT: usize = 3;
+const FIELD_PATH: usize = 4;
+const FIELD_COUNT: usize = 5;
+
+pub struct ExportForm {
+    pub field: usize,
+    /// Export topics under `prefix` instead of the current tab's widgets
+    pub by_prefix: bool,
+    pub prefix: String,
+    pub range: usize,
+    pub format: Format,
+    pub path: String,
+    pub error: Option<String>,
+}
+
+impl ExportForm {
+    pub fn new() -> Self {
+        Self {
+            field: FIELD_TOPICS,
+            by_prefix: false,
+            prefix: String::new(),
+            range: 1,
+            format: Format::Csv,
+            path: String::new(),
+            error: None,
+        }
+    }
+}
+
+impl App {
+    pub fn enter_export(&mut self) {
+        let format = self.export.format;
+        self.export.path = self
+            .record_dir
+            .join(recorder::timestamped_file_name(format.extension()))
+            .display()
+            .to_string();
+        self.export.error = None;
+        self.mode = Window::Export;
+    }
+
+    pub fn exit_export(&mut self) {
+        self.mode = Window::Main;
+    }
+
+    pub fn export_move_field(&mut self, delta: isize) {
+        let field = self.export.field as isize + delta;
+        self.export.field = field.rem_euclid(FIELD_COUNT as isize) as usize;
+    }
+
+    /// Changes the focused choice with left/right
+    pub fn export_adjust(&mut self, delta: isize) {
+        let form = &mut self.export;
+        match form.field {
+            FIELD_TOPICS => form.by_prefix = !form.by_prefix,
+            FIELD_RANGE => {
+                form.range =
+                    (form.range as isize + delta).clamp(0, RANGES.len() as isize - 1) as usize
+            }
+            FIELD_FORMAT => {
+                form.format = form.format.next();
+                form.path = Path::new(&form.path)
+                    .with_extension(form.format.extension())
+                    .display()
+                    .to_string();
+            }
+            _ => {}
+        }
+    }
+
+    /// Text fields take typed characters; anything else ignores them
+    pub fn export_input(&mut self, c: char) {
+        let form = &mut self.export;
+        form.error = None;
+        match form.field {
+            FIELD_PREFIX => form.prefix.push(c),
+            FIELD_PATH => form.path.push(c),
+            _ => {}
+        }
+    }
+
+    pub fn export_backspace(&mut self) {
+        let form = &mut self.export;
+        form.error = None;
+        match form.field {
+            FIELD_PREFIX => form.prefix.pop(),
+            FIELD_PATH => form.path.pop(),
+            _ => None,
+        };
+    }
+
+    /// Exports the graph history, so only numeric topics can be exported; the rest are skipped
+    pub fn run_export(&mut self) {
+        let topics: Vec<String> = if self.export.by_prefix {
+            self.history
+                .topics()
+                .filter(|t| t.starts_with(&self.export.prefix))
+                .cloned()
+                .collect()
+        } else {
+            self.widgets().iter().map(|w| w.topic.clone()).collect()
+        };
+
+        let since = self.now() - RANGES[self.export.range];
+        let mut samples = Samples::new();
+        for topic in topics {
+            let Some(history) = self.history.get(&topic) else {
+                continue;
+            };
+            let ty = self.topic_types.get(&topic);
+            let values: Vec<_> = history
+                .iter()
+                .filter(|(t, _)| *t >= since)
+                .map(|(t, v)| (*t, numeric_value(ty, *v)))
+                .collect();
+            if !values.is_empty() {
+                samples.insert(topic, values);
+            }
+        }
+        if samples.is_empty() {
+            self.export.error = Some("No numeric history for those topics".to_string());
+            return;
+        }
+
+        let path = self.export.path.clone();
+        let result = File::create(&path)
+            .map(BufWriter::new)
+            .and_then(|mut out| export::write(&mut out, &samples, self.export.format));
+        match result {
+            Ok(()) => {
+                log::info!("Exported {} topics to {}", samples.len(), path);
+                self.set_copy_message(format!("Exported {} topics to {}", samples.len(), path));
+                self.exit_export();
+            }
+            Err(e) => {
+                log::error!("Failed to export to {}: {}", path, e);
+                self.export.error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+// History keeps numbers as f64, so give ints and floats back their announced type
+fn numeric_value(ty: Option<&NtType>, value: f64) -> NtValue {
+    match ty {
+        Some(NtType::Int) => NtValue::Int(value as i64),
+        Some(NtType::Float) => NtValue::Float(value as f32),
+        _ => NtValue::Double(value),
+    }
+}
+
+pub fn render_export(f: &mut ratatui::Frame, app: &App, size: Rect) {
+    let popup_width = (size.width / 2).max(60);
+    let popup_height = 14;
+
+    let popup_x = (size.width - popup_width) / 2;
+    let popup_y = (size.height - popup_height) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Create a clear background for the popup
+    f.render_widget(Clear, popup_area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(9), // Fields
+            Constraint::Length(5), // Controls
+        ])
+        .split(popup_area);
+
+    let form = &app.export;
+    let cursor = if app.cursor_visible { "_" } else { " " };
+    let text_value = |value: &str, field: usize| {
+        if form.field == field {
+            format!("{}{}", value, cursor)
+        } else {
+            value.to_string()
+        }
+    };
+    let topics = if form.by_prefix {
+        "Topics under prefix"
+    } else {
+        "Widgets on this tab"
+    };
+    let rows = [
+        ("Topics", topics.to_string()),
+        ("Prefix", text_value(&form.prefix, FIELD_PREFIX)),
+        ("Range", format!("Last {}s", RANGES[form.range])),
+        ("Format", form.format.extension().to_uppercase()),
+        ("File", text_value(&form.path, FIELD_PATH)),
+    ];
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, value))| {
+            let value = if i == form.field {
+                Span::from(value).blue().bold()
+            } else if i == FIELD_PREFIX && !form.by_prefix {
+                Span::from(value).dark_gray()
+            } else {
+                Span::from(value)
+            };
+            Line::from(vec![format!("{:<8}", name).bold(), value])
+        })
+        .collect();
+    lines.push(Line::from("Only numeric topics keep history, others are skipped").dark_gray());
+    if let Some(error) = &form.error {
+        lines.push(Line::from(error.clone()).red());
+    }
+
+    let fields_box = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("Export Numeric History")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if form.error.is_some() {
+                    Color::Red
+                } else {
+                    Color::Blue
+                }))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Left);
+
+    let help_text = vec![
+        Line::from(vec![
+            "[".dim(),
+            "↑↓".blue().bold(),
+            "] ".dim(),
+            "Field".reset(),
+            "   [".dim(),
+            "←→".blue().bold(),
+            "] ".dim(),
+            "Change".reset(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "Enter".green().bold(),
+            "] ".dim(),
+            "Export".reset(),
+            "   [".dim(),
+            "Esc".red().bold(),
+            "] ".dim(),
+            "Cancel".reset(),
+        ]),
+    ];
+
+    let controls_box = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .title("Controls")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::new(1, 0, 0, 0)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(fields_box, layout[0]);
+    f.render_widget(controls_box, layout[1]);
+}
@@ -0,0 +1,121 @@
+use ratatui::{
+    layout::Rect,
+    style::Color,
+    symbols::Marker,
+    widgets::{
+        Block,
+        canvas::{Canvas, Context, Line, Rectangle},
+    },
+};
+
+use crate::config::{FieldConfig, Widget};
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+use crate::value::NtValue;
+
+/// `.type` of the tables Field2d publishes
+pub const FIELD2D_TYPE: &str = "Field2d";
+
+/// The object Field2d uses for the robot's own pose
+const ROBOT: &str = "Robot";
+const OBJECT_COLORS: [Color; 4] = [Color::Cyan, Color::Magenta, Color::Green, Color::LightRed];
+
+/// A field pose: x and y in meters, heading in radians
+type Pose = (f64, f64, f64);
+
+/// Draws the field with the robot and every other object in the Field2d table.
+/// Objects with several poses are drawn as a trajectory.
+pub fn render_field(f: &mut ratatui::Frame, app: &App, widget: &Widget, area: Rect, block: Block) {
+    let default_config = FieldConfig::default();
+    let config = widget.field.as_ref().unwrap_or(&default_config);
+
+    // Each object is a double array of (x, y, degrees) triples
+    let objects: Vec<(&str, Vec<Pose>)> = app
+        .sendable_entries(&widget.topic)
+        .into_iter()
+        .filter_map(|(name, value)| match value {
+            NtValue::DoubleArray(v) => Some((
+                name,
+                v.chunks_exact(3)
+                    .map(|p| (p[0], p[1], p[2].to_radians()))
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect();
+
+    if objects.is_empty() {
+        render_placeholder(f, area, block, "No poses");
+        return;
+    }
+
+    let half_robot = config.robot_size / 2.0;
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .x_bounds([0.0, config.length])
+        .y_bounds([0.0, config.width])
+        .paint(|ctx| {
+            ctx.draw(&Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: config.length,
+                height: config.width,
+                color: Color::DarkGray,
+            });
+            ctx.draw(&Line::new(
+                config.length / 2.0,
+                0.0,
+                config.length / 2.0,
+                config.width,
+                Color::DarkGray,
+            ));
+
+            let mut colors = OBJECT_COLORS.iter().cycle();
+            for (name, poses) in &objects {
+                if *name == ROBOT {
+                    continue;
+                }
+                let color = *colors.next().unwrap();
+                if poses.len() > 1 {
+                    for pair in poses.windows(2) {
+                        let ((x1, y1, _), (x2, y2, _)) = (pair[0], pair[1]);
+                        ctx.draw(&Line::new(x1, y1, x2, y2, color));
+                    }
+                } else {
+                    for pose in poses {
+                        draw_pose(ctx, *pose, half_robot, color);
+                    }
+                }
+            }
+
+            // The robot goes on its own layer so trajectories never hide it
+            ctx.layer();
+            for (_, poses) in objects.iter().filter(|(name, _)| *name == ROBOT) {
+                for pose in poses {
+                    draw_pose(ctx, *pose, half_robot, Color::Yellow);
+                }
+            }
+        });
+    f.render_widget(canvas, area);
+}
+
+/// Draws a square robot outline with a line from its center to the front edge
+fn draw_pose(ctx: &mut Context, (x, y, heading): Pose, half: f64, color: Color) {
+    let (sin, cos) = heading.sin_cos();
+    let corner = |dx: f64, dy: f64| (x + dx * cos - dy * sin, y + dx * sin + dy * cos);
+
+    let corners = [
+        corner(half, half),
+        corner(-half, half),
+        corner(-half, -half),
+        corner(half, -half),
+    ];
+    for (i, (x1, y1)) in corners.iter().enumerate() {
+        let (x2, y2) = corners[(i + 1) % corners.len()];
+        ctx.draw(&Line::new(*x1, *y1, x2, y2, color));
+    }
+
+    let (front_x, front_y) = corner(half, 0.0);
+    ctx.draw(&Line::new(x, y, front_x, front_y, color));
+}
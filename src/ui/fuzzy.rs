@@ -6,7 +6,6 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph};
 
-use crate::config::{Widget, WidgetType};
 use crate::ui::Window;
 use crate::ui::app::App;
 
@@ -63,14 +62,7 @@ impl App {
             }
 
             // Otherwise create a new widget
-            let widget = Widget {
-                topic: selected_topic.clone(),
-                label: selected_topic.clone(),
-                widget_type: WidgetType::Text,
-                position: self.find_next_grid_position(),
-            };
-
-            let _ = self.add_widget(widget);
+            self.add_widget_for_topic(&selected_topic);
             self.exit_fuzzy_search();
             Some(selected_topic)
         } else {
@@ -85,6 +77,8 @@ pub struct FuzzySearch {
     pub matches: Vec<String>,
     pub selected_index: usize,
     pub list_state: ListState,
+    /// Where the results list was last drawn, for mouse hit-testing
+    pub list_area: Rect,
 }
 
 impl FuzzySearch {
@@ -97,11 +91,20 @@ impl FuzzySearch {
             matches: Vec::new(),
             selected_index: 0,
             list_state,
+            list_area: Rect::default(),
         }
     }
 
     pub fn update_matches(&mut self, available_topics: &HashSet<String>) {
         let mut vec = available_topics.iter().cloned().collect::<Vec<_>>();
+        // Sendable tables (choosers and the like) get a widget of their own
+        vec.extend(
+            available_topics
+                .iter()
+                .filter_map(|t| t.strip_suffix("/.type"))
+                .filter(|table| !available_topics.contains(*table))
+                .map(str::to_string),
+        );
         if self.input.is_empty() {
             // If empty query, show all topics sorted alphabetically
             vec.sort();
@@ -128,6 +131,13 @@ impl FuzzySearch {
         self.matches.get(self.selected_index)
     }
 
+    pub fn select(&mut self, index: usize) {
+        if index < self.matches.len() {
+            self.selected_index = index;
+            self.list_state.select(Some(index));
+        }
+    }
+
     pub fn move_selection(&mut self, offset: isize) {
         if self.matches.is_empty() {
             return;
@@ -220,6 +230,7 @@ pub fn render_fuzzy_search(f: &mut ratatui::Frame, app: &mut App, size: Rect) {
         })
         .collect();
 
+    app.fuzzy_search.list_area = results_block.inner(popup_layout[1]);
     let list = List::new(items).block(results_block);
 
     // Now we can properly access list_state as mutable
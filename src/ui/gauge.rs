@@ -0,0 +1,50 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Gauge},
+};
+
+use crate::config::{GaugeConfig, Widget};
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+
+pub fn render_gauge(f: &mut ratatui::Frame, app: &App, widget: &Widget, area: Rect, block: Block) {
+    let default_config = GaugeConfig::default();
+    let config = widget.gauge.as_ref().unwrap_or(&default_config);
+
+    let Some(value) = app.values.get(&widget.topic).and_then(|v| v.as_f64()) else {
+        render_placeholder(f, area, block, "No value");
+        return;
+    };
+
+    let ratio = if config.max > config.min {
+        ((value - config.min) / (config.max - config.min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let gauge = Gauge::default()
+        .block(block)
+        .gauge_style(
+            Style::default()
+                .fg(zone_color(config, value))
+                .bg(Color::Black),
+        )
+        .ratio(ratio)
+        .label(format!("{:.2}", value));
+    f.render_widget(gauge, area);
+}
+
+fn zone_color(config: &GaugeConfig, value: f64) -> Color {
+    let in_zone = |zone: Option<[f64; 2]>| {
+        zone.is_some_and(|[from, to]| value >= from.min(to) && value <= from.max(to))
+    };
+
+    if in_zone(config.danger) {
+        Color::Red
+    } else if in_zone(config.warning) {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::{HashMap, VecDeque};
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, Chart, Dataset, GraphType},
+};
+
+use crate::config::{GraphConfig, Widget};
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+
+// Keep enough history for the widest graph anyone is likely to configure
+const HISTORY_SECS: f64 = 300.0;
+const MAX_SAMPLES: usize = 20_000;
+
+/// Rolling history of numeric topic values, as (seconds since start, value) pairs
+pub struct History {
+    samples: HashMap<String, VecDeque<(f64, f64)>>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            samples: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, topic: &str, time: f64, value: f64) {
+        let samples = self.samples.entry(topic.to_string()).or_default();
+        samples.push_back((time, value));
+
+        // Drop samples that are too old or over the cap
+        while let Some(&(t, _)) = samples.front() {
+            if time - t > HISTORY_SECS || samples.len() > MAX_SAMPLES {
+                samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn get(&self, topic: &str) -> Option<&VecDeque<(f64, f64)>> {
+        self.samples.get(topic)
+    }
+
+    /// Topics with numeric samples
+    pub fn topics(&self) -> impl Iterator<Item = &String> {
+        self.samples.keys()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+pub fn render_graph(f: &mut ratatui::Frame, app: &App, widget: &Widget, area: Rect, block: Block) {
+    let default_config = GraphConfig::default();
+    let config = widget.graph.as_ref().unwrap_or(&default_config);
+    let now = app.now();
+
+    // Shift timestamps so the newest sample sits at x = 0
+    let data: Vec<(f64, f64)> = app
+        .history
+        .get(&widget.topic)
+        .map(|samples| {
+            samples
+                .iter()
+                .filter(|(t, _)| now - t <= config.window_secs)
+                .map(|(t, v)| (t - now, *v))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if data.is_empty() {
+        render_placeholder(f, area, block, "No data");
+        return;
+    }
+
+    let (y_min, y_max) = y_bounds(config, &data);
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::LightYellow))
+        .data(&data);
+
+    let mut x_axis = Axis::default()
+        .style(Style::default().fg(Color::DarkGray))
+        .bounds([-config.window_secs, 0.0])
+        .labels([
+            Span::raw(format!("-{}s", config.window_secs)),
+            Span::raw("now"),
+        ]);
+    if let Some(label) = &config.x_label {
+        x_axis = x_axis.title(label.clone().gray());
+    }
+
+    let mut y_axis = Axis::default()
+        .style(Style::default().fg(Color::DarkGray))
+        .bounds([y_min, y_max])
+        .labels([
+            Span::raw(format_axis_value(y_min)),
+            Span::raw(format_axis_value((y_min + y_max) / 2.0)),
+            Span::raw(format_axis_value(y_max)),
+        ]);
+    if let Some(label) = &config.y_label {
+        y_axis = y_axis.title(label.clone().gray());
+    }
+
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+    f.render_widget(chart, area);
+}
+
+fn y_bounds(config: &GraphConfig, data: &[(f64, f64)]) -> (f64, f64) {
+    if let (Some(min), Some(max)) = (config.y_min, config.y_max) {
+        return (min, max);
+    }
+
+    let data_min = data.iter().map(|(_, v)| *v).fold(f64::INFINITY, f64::min);
+    let data_max = data
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    // Pad the range a bit so the line doesn't hug the border
+    let padding = ((data_max - data_min) * 0.1).max(0.5);
+    (
+        config.y_min.unwrap_or(data_min - padding),
+        config.y_max.unwrap_or(data_max + padding),
+    )
+}
+
+fn format_axis_value(value: f64) -> String {
+    if value.abs() >= 100.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use ratatui::{
+    layout::Rect,
+    style::Color,
+    symbols::Marker,
+    widgets::{
+        Block,
+        canvas::{Canvas, Context, Line, Points},
+    },
+};
+
+use crate::config::Widget;
+use crate::ui::app::App;
+use crate::ui::render_placeholder;
+use crate::value::NtValue;
+
+/// `.type` of the tables Mechanism2d publishes
+pub const MECHANISM2D_TYPE: &str = "Mechanism2d";
+
+/// Everything under a Mechanism2d table, by path relative to the table
+struct Mechanism<'a> {
+    entries: BTreeMap<&'a str, &'a NtValue>,
+}
+
+impl<'a> Mechanism<'a> {
+    fn new(app: &'a App, table: &str) -> Self {
+        let prefix = format!("{}/", table);
+        let entries = app
+            .values
+            .iter()
+            .filter_map(|(topic, value)| Some((topic.strip_prefix(&prefix)?, value)))
+            .collect();
+        Self { entries }
+    }
+
+    fn get(&self, path: &str) -> Option<&'a NtValue> {
+        self.entries.get(path).copied()
+    }
+
+    fn number(&self, path: &str) -> Option<f64> {
+        self.get(path)?.as_f64()
+    }
+
+    fn color(&self, path: &str) -> Option<Color> {
+        match self.get(path)? {
+            NtValue::String(s) => Color::from_str(s).ok(),
+            _ => None,
+        }
+    }
+
+    /// Names of the subtables directly under `path` ("" for the mechanism itself)
+    fn children(&self, path: &str) -> BTreeSet<&'a str> {
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+        self.entries
+            .keys()
+            .filter_map(|key| key.strip_prefix(prefix.as_str()))
+            .filter_map(|rest| rest.split_once('/').map(|(child, _)| child))
+            .collect()
+    }
+
+    /// Draws the ligaments under `path`, which starts at `origin` facing `heading` degrees
+    fn draw_ligaments(&self, ctx: &mut Context, path: &str, origin: (f64, f64), heading: f64) {
+        for child in self.children(path) {
+            let child_path = format!("{}/{}", path, child);
+            let (Some(angle), Some(length)) = (
+                self.number(&format!("{}/angle", child_path)),
+                self.number(&format!("{}/length", child_path)),
+            ) else {
+                continue;
+            };
+
+            // Ligament angles are relative to the ligament they hang off
+            let heading = heading + angle;
+            let (sin, cos) = heading.to_radians().sin_cos();
+            let end = (origin.0 + length * cos, origin.1 + length * sin);
+            let color = self
+                .color(&format!("{}/color", child_path))
+                .unwrap_or(Color::White);
+            // Terminal lines only come in one thickness, so `weight` is ignored
+            ctx.draw(&Line::new(origin.0, origin.1, end.0, end.1, color));
+
+            self.draw_ligaments(ctx, &child_path, end, heading);
+        }
+    }
+}
+
+/// Draws each root of the mechanism and the ligament tree hanging off it
+pub fn render_mechanism(
+    f: &mut ratatui::Frame,
+    app: &App,
+    widget: &Widget,
+    area: Rect,
+    block: Block,
+) {
+    let mechanism = Mechanism::new(app, &widget.topic);
+    let dims = match mechanism.get("dims") {
+        Some(NtValue::DoubleArray(dims)) if dims.len() == 2 && dims[0] > 0.0 && dims[1] > 0.0 => {
+            (dims[0], dims[1])
+        }
+        _ => {
+            render_placeholder(f, area, block, "No mechanism");
+            return;
+        }
+    };
+
+    // Roots are the subtables with a position
+    let roots: Vec<(&str, (f64, f64))> = mechanism
+        .children("")
+        .into_iter()
+        .filter_map(|root| {
+            let x = mechanism.number(&format!("{}/x", root))?;
+            let y = mechanism.number(&format!("{}/y", root))?;
+            Some((root, (x, y)))
+        })
+        .collect();
+
+    let canvas = Canvas::default()
+        .block(block)
+        .marker(Marker::Braille)
+        .background_color(mechanism.color("backgroundColor").unwrap_or(Color::Reset))
+        .x_bounds([0.0, dims.0])
+        .y_bounds([0.0, dims.1])
+        .paint(|ctx| {
+            for (root, origin) in &roots {
+                mechanism.draw_ligaments(ctx, root, *origin, 0.0);
+            }
+            // Pivots go on a later layer so the ligaments don't cover them
+            ctx.layer();
+            let pivots: Vec<(f64, f64)> = roots.iter().map(|(_, origin)| *origin).collect();
+            ctx.draw(&Points {
+                coords: &pivots,
+                color: Color::Gray,
+            });
+        });
+    f.render_widget(canvas, area);
+}
@@ -1,6 +1,23 @@
+pub mod alerts;
 pub mod app;
+pub mod boolean;
+pub mod browser;
+pub mod chooser;
+pub mod command;
+pub mod console;
 pub mod edit;
+pub mod export;
+pub mod field;
 pub mod fuzzy;
+pub mod gauge;
+pub mod graph;
+pub mod mechanism;
+pub mod mouse;
+pub mod move_resize;
+pub mod pid;
+pub mod playback;
+pub mod recording;
+pub mod tabs;
 use app::App;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
@@ -8,24 +25,27 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use fuzzy::render_fuzzy_search;
-use log::info;
+use log::{LevelFilter, info};
 use ratatui::{
     Terminal,
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
 };
 use std::{
     io,
+    path::PathBuf,
     time::{Duration, Instant},
 };
-use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::{Receiver, Sender};
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
-    config::{GridPosition, WidgetType},
+    config::{Config, GridPosition, WidgetType},
     nt::NtUpdate,
+    playback::PlaybackControl,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -41,9 +61,34 @@ pub enum Window {
     FuzzySearch,
     CellConfig,
     LabelEdit,
+    ValueEdit,
+    Console,
+    TopicBrowser,
+    TabName,
+    TabDelete,
+    MoveResize,
+    Export,
+    Chooser,
+    PidTune,
 }
 
-pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
+/// Settings for the dashboard that come from the command line
+pub struct Options {
+    pub read_only: bool,
+    /// File to start recording to right away
+    pub record: Option<PathBuf>,
+    /// Directory for recordings started with `r`
+    pub record_dir: PathBuf,
+    /// Transport controls go to the player here when replaying a log
+    pub playback_control: Option<UnboundedSender<PlaybackControl>>,
+}
+
+pub fn run_ui(
+    mut receiver: Receiver<NtUpdate>,
+    sender: Sender<NtUpdate>,
+    config: Config,
+    options: Options,
+) -> Result<(), io::Error> {
     let mut animation_counter = 0;
     // Setup terminal
     enable_raw_mode()?;
@@ -53,7 +98,10 @@ pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state
-    let mut app = App::new();
+    let mut app = App::new(sender, config, &options);
+    if let Some(path) = options.record {
+        app.start_recording(path);
+    }
 
     // Main loop
     let tick_rate = Duration::from_millis(5);
@@ -76,21 +124,60 @@ pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
         // Key bindings
         ////////////////////////////////////////
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                app.handle_mouse(mouse);
+            }
+            if let Event::Key(key) = event {
                 // Update activity timestamp for any key press
                 app.update_activity();
 
                 match app.mode {
+                    // Second key of `gt`/`gT`
+                    Window::Main if app.pending_g => {
+                        app.pending_g = false;
+                        match key.code {
+                            KeyCode::Char('t') => app.next_tab(),
+                            KeyCode::Char('T') => app.prev_tab(),
+                            _ => {}
+                        }
+                    }
                     Window::Main => match key.code {
                         KeyCode::Char('q') => break,
                         KeyCode::Char('a') => app.enter_fuzzy_search(),
+                        KeyCode::Char(' ') if app.playback.is_some() => {
+                            app.send_playback(PlaybackControl::TogglePlay);
+                        }
                         KeyCode::Char(' ') => app.toggle_pause(),
+                        KeyCode::Char('[') => app.send_playback(PlaybackControl::Seek(-5.0)),
+                        KeyCode::Char(']') => app.send_playback(PlaybackControl::Seek(5.0)),
+                        KeyCode::Char('{') => app.send_playback(PlaybackControl::Seek(-30.0)),
+                        KeyCode::Char('}') => app.send_playback(PlaybackControl::Seek(30.0)),
+                        KeyCode::Char('.') => app.send_playback(PlaybackControl::Step),
+                        KeyCode::Char('-') => app.send_playback(PlaybackControl::Speed(0.5)),
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            app.send_playback(PlaybackControl::Speed(2.0));
+                        }
                         KeyCode::Char('h') => app.move_selection(0, -1),
                         KeyCode::Char('j') => app.move_selection(1, 0),
                         KeyCode::Char('k') => app.move_selection(-1, 0),
                         KeyCode::Char('l') => app.move_selection(0, 1),
+                        KeyCode::PageDown => app.move_selection(app.visible_rows as isize, 0),
+                        KeyCode::PageUp => app.move_selection(-(app.visible_rows as isize), 0),
                         KeyCode::Char('y') => app.copy_selected_value(),
+                        KeyCode::Char('p') => app.enter_value_edit(),
                         KeyCode::Char('D') => app.delete_selected_widget(),
+                        KeyCode::Char('c') => app.enter_console(),
+                        KeyCode::Char('b') => app.enter_topic_browser(),
+                        KeyCode::Char('r') => app.toggle_recording(),
+                        KeyCode::Char('e') => app.enter_export(),
+                        KeyCode::Char('g') => app.pending_g = true,
+                        KeyCode::Char(c @ '1'..='9') => app.select_tab(c as usize - '1' as usize),
+                        KeyCode::Char('N') => app.enter_tab_new(),
+                        KeyCode::Char('R') => app.enter_tab_rename(),
+                        KeyCode::Char('X') => app.enter_tab_delete(),
+                        KeyCode::Char('<') => app.move_tab(-1),
+                        KeyCode::Char('>') => app.move_tab(1),
                         KeyCode::Enter => app.enter_cell_config(),
                         _ => {}
                     },
@@ -104,6 +191,9 @@ pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
                             // Edit label - enter label edit mode
                             app.enter_label_edit();
                         }
+                        KeyCode::Char('t') => app.cycle_widget_type(),
+                        KeyCode::Char('p') => app.enter_value_edit(),
+                        KeyCode::Char('m') => app.enter_move_resize(),
                         KeyCode::Char('D') => {
                             app.delete_selected_widget();
                             app.exit_cell_config();
@@ -152,36 +242,160 @@ pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
                         }
                         _ => {}
                     },
+                    Window::Console if app.console.searching => match key.code {
+                        KeyCode::Esc => {
+                            app.console.search.clear();
+                            app.console.searching = false;
+                        }
+                        KeyCode::Enter => app.console.searching = false,
+                        KeyCode::Backspace => {
+                            app.console.search.pop();
+                            app.console.scroll = 0;
+                        }
+                        KeyCode::Char(c) => {
+                            app.console.search.push(c);
+                            app.console.scroll = 0;
+                        }
+                        _ => {}
+                    },
+                    Window::Console => match key.code {
+                        KeyCode::Esc | KeyCode::Char('c') => app.exit_console(),
+                        KeyCode::Char('j') | KeyCode::Down => app.console.scroll_by(-1),
+                        KeyCode::Char('k') | KeyCode::Up => app.console.scroll_by(1),
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.console.scroll_by(-10);
+                        }
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.console.scroll_by(10);
+                        }
+                        KeyCode::PageDown => app.console.scroll_by(-10),
+                        KeyCode::PageUp => app.console.scroll_by(10),
+                        KeyCode::Char('g') => app.console.scroll = usize::MAX,
+                        KeyCode::Char('G') => app.console.scroll = 0,
+                        KeyCode::Char('1') => app.console.set_level(LevelFilter::Error),
+                        KeyCode::Char('2') => app.console.set_level(LevelFilter::Warn),
+                        KeyCode::Char('3') => app.console.set_level(LevelFilter::Info),
+                        KeyCode::Char('4') => app.console.set_level(LevelFilter::Debug),
+                        KeyCode::Char('5') => app.console.set_level(LevelFilter::Trace),
+                        KeyCode::Char('/') => app.console.searching = true,
+                        KeyCode::Char('C') => app.clear_console(),
+                        _ => {}
+                    },
+                    Window::TopicBrowser => match key.code {
+                        KeyCode::Esc | KeyCode::Char('b') => app.exit_topic_browser(),
+                        KeyCode::Char('j') | KeyCode::Down => app.browser_move(1),
+                        KeyCode::Char('k') | KeyCode::Up => app.browser_move(-1),
+                        KeyCode::Char('l') | KeyCode::Right => app.browser_expand(),
+                        KeyCode::Char('h') | KeyCode::Left => app.browser_collapse(),
+                        KeyCode::Enter | KeyCode::Char(' ') => app.browser_toggle(),
+                        KeyCode::Char('a') => app.browser_add_widget(),
+                        KeyCode::Char('y') => app.browser_copy_path(),
+                        KeyCode::Char('p') => app.browser_publish(),
+                        _ => {}
+                    },
+                    Window::MoveResize => match key.code {
+                        KeyCode::Esc => app.exit_move_resize(),
+                        KeyCode::Enter => app.commit_move_resize(),
+                        KeyCode::Char('h') => app.shift_preview(0, -1),
+                        KeyCode::Char('j') => app.shift_preview(1, 0),
+                        KeyCode::Char('k') => app.shift_preview(-1, 0),
+                        KeyCode::Char('l') => app.shift_preview(0, 1),
+                        KeyCode::Char('H') => app.resize_preview(0, -1),
+                        KeyCode::Char('J') => app.resize_preview(1, 0),
+                        KeyCode::Char('K') => app.resize_preview(-1, 0),
+                        KeyCode::Char('L') => app.resize_preview(0, 1),
+                        _ => {}
+                    },
+                    Window::TabName => match key.code {
+                        KeyCode::Esc => app.exit_tab_name(),
+                        KeyCode::Enter => app.save_tab_name(),
+                        KeyCode::Backspace => {
+                            app.tab_name_edit.pop();
+                        }
+                        KeyCode::Char(c) => {
+                            app.tab_name_edit.push(c);
+                        }
+                        _ => {}
+                    },
+                    Window::Export => match key.code {
+                        KeyCode::Esc => app.exit_export(),
+                        KeyCode::Enter => app.run_export(),
+                        KeyCode::Up | KeyCode::BackTab => app.export_move_field(-1),
+                        KeyCode::Down | KeyCode::Tab => app.export_move_field(1),
+                        KeyCode::Left => app.export_adjust(-1),
+                        KeyCode::Right => app.export_adjust(1),
+                        KeyCode::Backspace => app.export_backspace(),
+                        KeyCode::Char(c) => app.export_input(c),
+                        _ => {}
+                    },
+                    Window::Chooser => match key.code {
+                        KeyCode::Esc => app.exit_chooser(),
+                        KeyCode::Enter => app.publish_choice(),
+                        KeyCode::Char('k') | KeyCode::Up => app.chooser_move(-1),
+                        KeyCode::Char('j') | KeyCode::Down => app.chooser_move(1),
+                        _ => {}
+                    },
+                    Window::PidTune => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => app.exit_pid_tune(),
+                        KeyCode::Char('k') | KeyCode::Up => app.pid_move_field(-1),
+                        KeyCode::Char('j') | KeyCode::Down => app.pid_move_field(1),
+                        KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('-') => {
+                            app.pid_adjust(-1.0);
+                        }
+                        KeyCode::Char('l') | KeyCode::Right | KeyCode::Char('+') => {
+                            app.pid_adjust(1.0);
+                        }
+                        KeyCode::Char('H') => app.pid_adjust(-10.0),
+                        KeyCode::Char('L') => app.pid_adjust(10.0),
+                        KeyCode::Char('[') => app.pid_scale_step(0.1),
+                        KeyCode::Char(']') => app.pid_scale_step(10.0),
+                        KeyCode::Enter => app.pid_edit_field(),
+                        _ => {}
+                    },
+                    Window::TabDelete => match key.code {
+                        KeyCode::Char('y') => app.delete_current_tab(),
+                        KeyCode::Char('n') | KeyCode::Esc => app.mode = Window::Main,
+                        _ => {}
+                    },
+                    Window::ValueEdit => match key.code {
+                        KeyCode::Esc => app.exit_value_edit(),
+                        KeyCode::Enter => app.publish_value(),
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.value_edit.clear();
+                        }
+                        KeyCode::Backspace => {
+                            app.value_edit.pop();
+                            app.value_edit_error = None;
+                        }
+                        KeyCode::Char(c) => {
+                            app.value_edit.push(c);
+                            app.value_edit_error = None;
+                        }
+                        _ => {}
+                    },
                 }
             }
         }
 
         // Check for updates from NT
         while let Ok(update) = receiver.try_recv() {
-            match update {
-                NtUpdate::Subscribed(key, value) => {
-                    let k = key.clone();
-                    // Only update values if not paused
-                    if !app.paused {
-                        app.values.insert(key, value);
-                    }
-                    // Always update connection status and available topics
-                    app.connection_status = ConnectionStatus::Connected;
-                    app.available_topics.insert(k);
-                    if app.mode == Window::FuzzySearch {
-                        app.fuzzy_search.update_matches(&app.available_topics);
-                    }
-                }
-                NtUpdate::ConnectionStatus(status) => {
-                    app.connection_status = status;
-                }
-                NtUpdate::Publish(_, _) => {}
-            }
+            app.apply_update(update);
         }
+        app.drain_recording_feed();
 
         // Tick handling
         if last_tick.elapsed() >= tick_rate {
-            if animation_counter % 50 == 0 && (app.mode == Window::FuzzySearch || app.mode == Window::LabelEdit) {
+            if animation_counter % 50 == 0
+                && matches!(
+                    app.mode,
+                    Window::FuzzySearch
+                        | Window::LabelEdit
+                        | Window::ValueEdit
+                        | Window::Console
+                        | Window::TabName
+                        | Window::Export
+                )
+            {
                 app.cursor_visible = !app.cursor_visible;
             }
             animation_counter += 1;
@@ -189,6 +403,8 @@ pub fn run_ui(mut receiver: Receiver<NtUpdate>) -> Result<(), io::Error> {
         }
     }
 
+    app.stop_recording();
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(
@@ -208,12 +424,16 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(1), // Tab bar
             Constraint::Min(3),    // Main content
             Constraint::Length(3), // Status bar
             Constraint::Length(1), // Help text
         ])
         .split(size);
 
+    // Render the tab bar, inset to line up with the grid
+    tabs::render_tab_bar(f, app, main_layout[0].inner(Margin::new(1, 0)));
+
     // Add padding to the sides
     let padded_area = Layout::default()
         .direction(Direction::Horizontal)
@@ -222,68 +442,82 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
             Constraint::Min(8),    // Content
             Constraint::Length(1), // Right padding
         ])
-        .split(main_layout[0])[1];
+        .split(main_layout[1])[1];
 
     // Calculate how many rows can fit in the available space
-    // Each row needs 3 units of height
-    let available_height = padded_area.height;
-    let max_rows = (available_height / 3) as usize;
-
-    // Update the app's max_rows value
-    app.max_rows = max_rows;
+    let row_height = app.config.grid.row_height.max(1);
+    let columns = app.columns();
+    let visible_rows = (padded_area.height / row_height) as usize;
+
+    // The grid is as tall as its content plus a spare row to add widgets into,
+    // and at least as tall as the screen
+    let content_rows = app
+        .widgets()
+        .iter()
+        .map(|w| w.position.row + w.position.row_span)
+        .max()
+        .unwrap_or(0);
+    app.max_rows = visible_rows.max(content_rows + 1);
+    app.visible_rows = visible_rows;
 
-    // Create constraints for the rows
-    let mut row_constraints = Vec::new();
-    for _ in 0..max_rows {
-        row_constraints.push(Constraint::Length(3));
+    // Keep the selection (or the widget being moved) inside the viewport
+    let focus = match &app.move_preview {
+        Some((_, pos)) => Some((pos.row, pos.row + pos.row_span - 1)),
+        None => app.selected_cell.map(|(row, _)| (row, row)),
+    };
+    if let Some((top, bottom)) = focus {
+        if bottom >= app.scroll_row + visible_rows {
+            app.scroll_row = (bottom + 1).saturating_sub(visible_rows);
+        }
+        if top < app.scroll_row {
+            app.scroll_row = top;
+        }
     }
+    app.scroll_row = app
+        .scroll_row
+        .min(app.max_rows.saturating_sub(visible_rows));
+    let scroll_row = app.scroll_row;
 
-    // Create a grid layout with fixed row heights (3 rows each) in the main content area
+    // Create constraints for the visible rows
+    let row_constraints = vec![Constraint::Length(row_height); visible_rows];
+    let column_constraints = vec![Constraint::Ratio(1, columns as u32); columns];
+
+    // Create a grid layout with fixed row heights in the main content area
     let grid_constraints = Layout::default()
         .direction(Direction::Vertical)
         .constraints(row_constraints)
         .split(padded_area);
 
+    // Cells of the visible rows; grid_cells[0] is grid row `scroll_row`
     let mut grid_cells = Vec::new();
     for row in grid_constraints.iter() {
         let cells = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(vec![
-                Constraint::Ratio(1, 5),
-                Constraint::Ratio(1, 5),
-                Constraint::Ratio(1, 5),
-                Constraint::Ratio(1, 5),
-                Constraint::Ratio(1, 5),
-            ])
+            .constraints(column_constraints.clone())
             .split(*row);
         grid_cells.push(cells.to_vec());
     }
+    // Kept on the app so mouse clicks can be mapped back to cells
+    app.grid_cells = grid_cells;
+    let grid_cells = &app.grid_cells;
 
-    // Check if we have enough space for all configured widgets
+    // Let the user know when part of the layout is scrolled out of view
     let mut warning_message = String::new();
-    let max_widget_row = app
-        .config
-        .widgets
-        .iter()
-        .map(|w| w.position.row)
-        .max()
-        .unwrap_or(0);
-
-    if max_widget_row >= max_rows {
+    if content_rows > visible_rows {
         warning_message = format!(
-            "Warning: Not enough space for all widgets! ({} rows needed)",
-            max_widget_row + 1
+            "Rows {}-{} of {}",
+            scroll_row + 1,
+            (scroll_row + visible_rows).min(content_rows),
+            content_rows
         );
     }
 
     // Render widgets based on their configured positions
-    for widget in &app.config.widgets {
+    for widget in app.widgets() {
         // Skip widgets that are outside the visible area
-        if widget.position.row >= max_rows {
+        let Some(widget_area) = get_widget_area(grid_cells, scroll_row, &widget.position) else {
             continue;
-        }
-
-        let widget_area = get_widget_area(&grid_cells, &widget.position);
+        };
 
         // Create the widget block with a transparent background
         let block = Block::default()
@@ -296,13 +530,23 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Black));
 
+        // Widgets bound to an unannounced topic show that instead of a stale value
+        if app.gone_topics.contains(&widget.topic) {
+            let text = Paragraph::new("Topic gone")
+                .block(block.border_style(Style::default().fg(Color::Red)))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(Color::Red).add_modifier(Modifier::DIM));
+            f.render_widget(text, widget_area);
+            continue;
+        }
+
         // Render the widget with the block
         match widget.widget_type {
             WidgetType::Text => {
-                let default = &"No value".to_string();
                 let value = app.values.get(&widget.topic);
+                let text = value.map_or("No value".to_string(), |v| v.to_string());
 
-                let text = Paragraph::new(value.unwrap_or(default).clone())
+                let text = Paragraph::new(text)
                     .block(block)
                     .alignment(Alignment::Center)
                     .style(Style::default().fg(if value.is_some() {
@@ -312,16 +556,26 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
                     }));
                 f.render_widget(text, widget_area);
             }
-            // Add other widget type rendering here
-            _ => {}
+            WidgetType::Graph => graph::render_graph(f, app, widget, widget_area, block),
+            WidgetType::Gauge => gauge::render_gauge(f, app, widget, widget_area, block),
+            WidgetType::Boolean => boolean::render_boolean(f, app, widget, widget_area, block),
+            WidgetType::Chooser => chooser::render_chooser(f, app, widget, widget_area, block),
+            WidgetType::Field => field::render_field(f, app, widget, widget_area, block),
+            WidgetType::Mechanism => {
+                mechanism::render_mechanism(f, app, widget, widget_area, block)
+            }
+            WidgetType::Command => command::render_command(f, app, widget, widget_area, block),
+            WidgetType::Subsystem => command::render_subsystem(f, app, widget, widget_area, block),
+            WidgetType::Pid => pid::render_pid(f, app, widget, widget_area, block),
+            WidgetType::Alerts => alerts::render_alerts(f, app, widget, widget_area, block),
         }
     }
 
     // Highlight the selected cell if in main mode and highlight is visible
     if app.mode == Window::Main && app.highlight_visible {
         if let Some((row, col)) = app.selected_cell {
-            if row < grid_cells.len() && col < grid_cells[0].len() {
-                let selected_area = grid_cells[row][col];
+            if row >= scroll_row && row - scroll_row < grid_cells.len() && col < columns {
+                let selected_area = grid_cells[row - scroll_row][col];
                 let highlight = Block::default().borders(Borders::ALL).border_style(
                     Style::default()
                         .fg(Color::Yellow)
@@ -332,15 +586,25 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
         }
     }
 
+    // Show where the widget will land while moving or resizing
+    if app.mode == Window::MoveResize
+        && let Some((_, pos)) = &app.move_preview
+        && let Some(preview_area) = get_widget_area(grid_cells, scroll_row, pos)
+    {
+        move_resize::render_move_preview(f, app, preview_area, pos);
+    }
+
     // Create status bar layout with multiple boxes
     let status_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Length(32), // Status
             Constraint::Length(20), // Topics count
+            Constraint::Length(14), // Alert errors
+            Constraint::Length(36), // Recording
             Constraint::Min(10),    // Copy message/warnings
         ])
-        .split(main_layout[1]);
+        .split(main_layout[2]);
 
     // Render connection status box
     let status_text = match app.connection_status {
@@ -355,7 +619,11 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
         ConnectionStatus::Disconnected => "Disconnected     ███".red().bold(),
     };
 
-    let status_box = Paragraph::new(Line::from(vec!["Status: ".bold(), status_text]))
+    let status_line = match &app.playback {
+        Some(playback) => playback::status_line(playback),
+        None => Line::from(vec!["Status: ".bold(), status_text]),
+    };
+    let status_box = Paragraph::new(status_line)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -385,6 +653,56 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
     )
     .alignment(Alignment::Left);
 
+    // Render alert error count box
+    let error_count = app.alert_error_count();
+    let errors_box = Paragraph::new(alerts::error_count_line(error_count))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if error_count > 0 {
+                    Color::Red
+                } else {
+                    Color::DarkGray
+                }))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Left);
+
+    // Render recording box
+    let mut recording_line = vec!["Rec: ".bold()];
+    let recording_color = match &app.recorder {
+        Some(recorder) => {
+            let secs = recorder.elapsed() as u64;
+            recording_line.push(
+                format!(
+                    "● {:02}:{:02} {:.1}MB",
+                    secs / 60,
+                    secs % 60,
+                    recorder.size() as f64 / 1_000_000.0
+                )
+                .red()
+                .bold(),
+            );
+            // Updates dropped before they reached the file
+            if recorder.missed() > 0 {
+                recording_line.push(format!(" {} missed", recorder.missed()).yellow());
+            }
+            Color::Red
+        }
+        None => {
+            recording_line.push("off".dark_gray());
+            Color::DarkGray
+        }
+    };
+    let recording_box = Paragraph::new(Line::from(recording_line))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(recording_color))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Left);
+
     // Render copy message/warnings box
     let mut info_text = Vec::new();
     if let Some(msg) = &app.copy_message {
@@ -406,10 +724,12 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
     // Render all status boxes
     f.render_widget(status_box, status_layout[0]);
     f.render_widget(topics_box, status_layout[1]);
-    f.render_widget(info_box, status_layout[2]);
+    f.render_widget(errors_box, status_layout[2]);
+    f.render_widget(recording_box, status_layout[3]);
+    f.render_widget(info_box, status_layout[4]);
 
     // Render help text with more colors
-    let help_text = Line::from(vec![
+    let mut help_text = Line::from(vec![
         "[".dim(),
         "q".red().bold(),
         "] ".dim(),
@@ -435,14 +755,73 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
         "] ".dim(),
         "Copy".reset(),
         "   [".dim(),
+        "p".magenta().bold(),
+        "] ".dim(),
+        "Set".reset(),
+        "   [".dim(),
         "D".red().bold(),
         "] ".dim(),
         "Delete".reset(),
+        "   [".dim(),
+        "c".blue().bold(),
+        "] ".dim(),
+        "Console".reset(),
+        "   [".dim(),
+        "b".blue().bold(),
+        "] ".dim(),
+        "Browse".reset(),
+        "   [".dim(),
+        "r".red().bold(),
+        "] ".dim(),
+        "Record".reset(),
+        "   [".dim(),
+        "e".magenta().bold(),
+        "] ".dim(),
+        "Export".reset(),
+        "   [".dim(),
+        "gt/gT".cyan().bold(),
+        "] ".dim(),
+        "Tabs".reset(),
+        "   [".dim(),
+        "N".green().bold(),
+        "] ".dim(),
+        "New Tab".reset(),
     ]);
+    if app.playback.is_some() {
+        help_text.spans.extend([
+            "   [".dim(),
+            "[ ] { }".cyan().bold(),
+            "] ".dim(),
+            "Seek".reset(),
+            "   [".dim(),
+            ".".cyan().bold(),
+            "] ".dim(),
+            "Step".reset(),
+            "   [".dim(),
+            "- +".cyan().bold(),
+            "] ".dim(),
+            "Speed".reset(),
+        ]);
+    }
+    if app.mode == Window::PidTune {
+        help_text = pid::help_line();
+    }
     let help_bar = Paragraph::new(help_text)
         .style(Style::default())
         .alignment(Alignment::Center);
-    f.render_widget(help_bar, main_layout[2]);
+    f.render_widget(help_bar, main_layout[3]);
+
+    // Render console over the dashboard if active
+    if app.mode == Window::Console {
+        console::render_console(f, app, main_layout[1]);
+    }
+
+    // Render topic browser over the dashboard, keeping it behind its publish popup
+    if app.mode == Window::TopicBrowser
+        || (app.mode == Window::ValueEdit && app.value_edit_return == Window::TopicBrowser)
+    {
+        browser::render_topic_browser(f, app, main_layout[1]);
+    }
 
     // Render fuzzy search popup if active
     if app.mode == Window::FuzzySearch {
@@ -458,24 +837,64 @@ fn ui(f: &mut ratatui::Frame, app: &mut App) {
     if app.mode == Window::LabelEdit {
         edit::render_label_edit(f, app, size);
     }
-}
 
-fn get_widget_area(grid_cells: &[Vec<Rect>], pos: &GridPosition) -> Rect {
-    let mut area = grid_cells[pos.row][pos.col];
+    // Render tab popups if active
+    if app.mode == Window::TabName {
+        tabs::render_tab_name_edit(f, app, size);
+    }
+    if app.mode == Window::TabDelete {
+        tabs::render_tab_delete(f, app, size);
+    }
 
-    // If widget spans multiple cells, combine their areas
-    if pos.row_span > 1 || pos.col_span > 1 {
-        let end_row = (pos.row + pos.row_span - 1).min(9);
-        let end_col = (pos.col + pos.col_span - 1).min(4);
-        let bottom_right = grid_cells[end_row][end_col];
+    // Render value edit popup if active
+    if app.mode == Window::ValueEdit {
+        edit::render_value_edit(f, app, size);
+    }
 
-        area = Rect::new(
-            area.x,
-            area.y,
-            bottom_right.x + bottom_right.width - area.x,
-            bottom_right.y + bottom_right.height - area.y,
-        );
+    // Render export popup if active
+    if app.mode == Window::Export {
+        export::render_export(f, app, size);
+    }
+
+    // Render chooser popup if active
+    if app.mode == Window::Chooser {
+        chooser::render_chooser_picker(f, app, size);
     }
+}
 
-    area
+/// Dimmed text filling a widget's cell while it has nothing to show yet
+pub fn render_placeholder(f: &mut ratatui::Frame, area: Rect, block: Block, text: &str) {
+    let text = Paragraph::new(text)
+        .block(block)
+        .centered()
+        .style(Style::default().fg(Color::Black));
+    f.render_widget(text, area);
+}
+
+/// Screen area of a widget, clipped to the visible rows. `grid_cells[0]` is grid row `scroll_row`.
+fn get_widget_area(
+    grid_cells: &[Vec<Rect>],
+    scroll_row: usize,
+    pos: &GridPosition,
+) -> Option<Rect> {
+    let last_visible_row = (scroll_row + grid_cells.len()).checked_sub(1)?;
+    let columns = grid_cells.first()?.len();
+
+    let start_row = pos.row.max(scroll_row);
+    let end_row = (pos.row + pos.row_span.max(1) - 1).min(last_visible_row);
+    if start_row > end_row || pos.col >= columns {
+        return None;
+    }
+    let end_col = (pos.col + pos.col_span.max(1) - 1).min(columns - 1);
+
+    // Combine the areas of the top-left and bottom-right cells
+    let top_left = grid_cells[start_row - scroll_row][pos.col];
+    let bottom_right = grid_cells[end_row - scroll_row][end_col];
+
+    Some(Rect::new(
+        top_left.x,
+        top_left.y,
+        bottom_right.x + bottom_right.width - top_left.x,
+        bottom_right.y + bottom_right.height - top_left.y,
+    ))
 }
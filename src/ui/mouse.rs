@@ -0,0 +1,135 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
+
+use crate::ui::Window;
+use crate::ui::app::App;
+
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Default)]
+pub struct MouseState {
+    /// Cell the left button went down on
+    pressed: Option<(usize, usize)>,
+    last_click: Option<(Instant, (usize, usize))>,
+    /// Offset of the grabbed cell from the dragged widget's top-left corner
+    drag_offset: Option<(usize, usize)>,
+}
+
+impl MouseState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl App {
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        let position = Position::new(event.column, event.row);
+        match self.mode {
+            Window::Main => match event.kind {
+                MouseEventKind::Down(MouseButton::Left) => self.mouse_down(position),
+                MouseEventKind::Drag(MouseButton::Left) => self.mouse_drag(position),
+                MouseEventKind::Up(MouseButton::Left) => self.mouse.pressed = None,
+                _ => {}
+            },
+            // Only entered from here while dragging; keyboard moves keep the mouse out of it
+            Window::MoveResize if self.mouse.drag_offset.is_some() => match event.kind {
+                MouseEventKind::Drag(MouseButton::Left) => self.mouse_drag(position),
+                MouseEventKind::Up(MouseButton::Left) => self.finish_drag(),
+                _ => {}
+            },
+            Window::FuzzySearch => match event.kind {
+                MouseEventKind::ScrollUp => self.fuzzy_search.move_selection(-1),
+                MouseEventKind::ScrollDown => self.fuzzy_search.move_selection(1),
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let area = self.fuzzy_search.list_area;
+                    if area.contains(position) {
+                        let index =
+                            self.fuzzy_search.list_state.offset() + (position.y - area.y) as usize;
+                        if index < self.fuzzy_search.matches.len() {
+                            self.fuzzy_search.select(index);
+                            self.handle_search_selection();
+                        }
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Grid cell under a screen position, accounting for the scrolled viewport
+    fn cell_at(&self, position: Position) -> Option<(usize, usize)> {
+        self.grid_cells.iter().enumerate().find_map(|(row, cells)| {
+            cells
+                .iter()
+                .position(|cell| cell.contains(position))
+                .map(|col| (self.scroll_row + row, col))
+        })
+    }
+
+    fn mouse_down(&mut self, position: Position) {
+        let Some(cell) = self.cell_at(position) else {
+            return;
+        };
+        self.selected_cell = Some(cell);
+        self.update_activity();
+        self.mouse.pressed = Some(cell);
+
+        let double_click = self
+            .mouse
+            .last_click
+            .is_some_and(|(time, last)| last == cell && time.elapsed() < DOUBLE_CLICK);
+        if double_click {
+            self.mouse.last_click = None;
+            self.mouse.pressed = None;
+            self.enter_cell_config();
+        } else {
+            self.mouse.last_click = Some((Instant::now(), cell));
+        }
+    }
+
+    fn mouse_drag(&mut self, position: Position) {
+        let Some((row, col)) = self.cell_at(position) else {
+            return;
+        };
+
+        // Start dragging once the pointer leaves the cell it went down on
+        if self.mode == Window::Main {
+            let Some(pressed) = self.mouse.pressed else {
+                return;
+            };
+            if pressed == (row, col) {
+                return;
+            }
+            let Some(index) = self.selected_widget_index() else {
+                return;
+            };
+            let pos = &self.widgets()[index].position;
+            self.mouse.drag_offset = Some((pressed.0 - pos.row, pressed.1 - pos.col));
+            self.enter_move_resize();
+        }
+
+        let (Some((offset_row, offset_col)), Some((_, pos))) =
+            (self.mouse.drag_offset, &self.move_preview)
+        else {
+            return;
+        };
+        let row_delta = row.saturating_sub(offset_row) as isize - pos.row as isize;
+        let col_delta = col.saturating_sub(offset_col) as isize - pos.col as isize;
+        self.shift_preview(row_delta, col_delta);
+        self.update_activity();
+    }
+
+    fn finish_drag(&mut self) {
+        self.mouse.pressed = None;
+        self.mouse.drag_offset = None;
+        self.commit_move_resize();
+
+        // Dropping onto another widget cancels the move
+        if self.mode == Window::MoveResize {
+            self.move_preview = None;
+            self.mode = Window::Main;
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear},
+};
+
+use crate::config::GridPosition;
+use crate::ui::Window;
+use crate::ui::app::App;
+
+impl App {
+    pub fn enter_move_resize(&mut self) {
+        if let Some(index) = self.selected_widget_index() {
+            self.move_preview = Some((index, self.widgets()[index].position.clone()));
+            self.mode = Window::MoveResize;
+        }
+    }
+
+    pub fn exit_move_resize(&mut self) {
+        self.move_preview = None;
+        self.mode = Window::CellConfig;
+    }
+
+    /// Shifts the preview by whole cells, keeping it inside the grid
+    pub fn shift_preview(&mut self, row_delta: isize, col_delta: isize) {
+        // Allow moving one row past the content so the grid can grow downwards
+        let max_rows = self.max_rows + 1;
+        let columns = self.columns();
+        if let Some((_, pos)) = &mut self.move_preview {
+            let max_row = max_rows.saturating_sub(pos.row_span) as isize;
+            let max_col = columns.saturating_sub(pos.col_span) as isize;
+            pos.row = (pos.row as isize + row_delta).clamp(0, max_row) as usize;
+            pos.col = (pos.col as isize + col_delta).clamp(0, max_col) as usize;
+        }
+    }
+
+    /// Grows or shrinks the preview from its top-left corner
+    pub fn resize_preview(&mut self, row_delta: isize, col_delta: isize) {
+        let max_rows = self.max_rows + 1;
+        let columns = self.columns();
+        if let Some((_, pos)) = &mut self.move_preview {
+            let max_row_span = max_rows.saturating_sub(pos.row).max(1) as isize;
+            let max_col_span = columns.saturating_sub(pos.col).max(1) as isize;
+            pos.row_span = (pos.row_span as isize + row_delta).clamp(1, max_row_span) as usize;
+            pos.col_span = (pos.col_span as isize + col_delta).clamp(1, max_col_span) as usize;
+        }
+    }
+
+    /// Whether the preview fits without overlapping any other widget
+    pub fn move_preview_is_valid(&self) -> bool {
+        let Some((index, pos)) = &self.move_preview else {
+            return false;
+        };
+        (pos.row..pos.row + pos.row_span).all(|row| {
+            (pos.col..pos.col + pos.col_span)
+                .all(|col| !self.is_position_occupied_by_other(row, col, Some(*index)))
+        })
+    }
+
+    pub fn commit_move_resize(&mut self) {
+        if !self.move_preview_is_valid() {
+            self.set_copy_message("Widget would overlap another widget".to_string());
+            return;
+        }
+        if let Some((index, pos)) = self.move_preview.take() {
+            self.selected_cell = Some((pos.row, pos.col));
+            self.widgets_mut()[index].position = pos;
+            self.config.save().unwrap_or_else(|e| {
+                log::error!("Failed to save config: {}", e);
+            });
+        }
+        self.mode = Window::Main;
+    }
+}
+
+pub fn render_move_preview(f: &mut ratatui::Frame, app: &App, area: Rect, pos: &GridPosition) {
+    let color = if app.move_preview_is_valid() {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    let preview = Block::default()
+        .title(Line::from(format!("{}x{}", pos.col_span, pos.row_span)).bold())
+        .title_bottom(Line::from(vec![
+            "hjkl".blue().bold(),
+            " move ".reset(),
+            "HJKL".blue().bold(),
+            " resize ".reset(),
+            "Enter".green().bold(),
+            " save".reset(),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+
+    f.render_widget(Clear, area);
+    f.render_widget(preview, area);
+}
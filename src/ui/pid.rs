@@ -0,0 +1,248 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+};
+
+use crate::config::Widget;
+use crate::ui::Window;
+use crate::ui::app::App;
+use crate::value::NtValue;
+
+/// `.type` of the tables PIDController publishes
+pub const PID_TYPE: &str = "PIDController";
+
+/// Editable entries of a PIDController, with their labels
+const FIELDS: [(&str, &str); 5] = [
+    ("p", "P"),
+    ("i", "I"),
+    ("d", "D"),
+    ("izone", "IZone"),
+    ("setpoint", "Setpoint"),
+];
+
+const MIN_STEP: f64 = 1e-6;
+const MAX_STEP: f64 = 1000.0;
+
+/// In-place tuning of the PIDController under `table`
+pub struct PidTuner {
+    pub table: String,
+    pub field: usize,
+    /// How much one increment changes the focused value
+    pub step: f64,
+    pub return_to: Window,
+}
+
+impl PidTuner {
+    pub fn new() -> Self {
+        Self {
+            table: String::new(),
+            field: 0,
+            step: 0.01,
+            return_to: Window::Main,
+        }
+    }
+}
+
+impl App {
+    fn pid_value(&self, table: &str, key: &str) -> Option<f64> {
+        self.values
+            .get(&format!("{}/{}", table, key))
+            .and_then(|v| v.as_f64())
+    }
+
+    pub fn enter_pid_tune(&mut self, table: String) {
+        if self.read_only {
+            self.set_copy_message("Read-only mode, publishing is disabled".to_string());
+            return;
+        }
+        // Keep the focused field and step when coming back to the same controller
+        if self.pid.table != table {
+            self.pid = PidTuner::new();
+            self.pid.table = table;
+        }
+        self.pid.return_to = self.mode.clone();
+        self.mode = Window::PidTune;
+    }
+
+    pub fn exit_pid_tune(&mut self) {
+        self.mode = self.pid.return_to.clone();
+    }
+
+    pub fn pid_move_field(&mut self, delta: isize) {
+        let field = self.pid.field as isize + delta;
+        self.pid.field = field.rem_euclid(FIELDS.len() as isize) as usize;
+    }
+
+    /// Multiplies the step, e.g. by 10 or 0.1
+    pub fn pid_scale_step(&mut self, factor: f64) {
+        self.pid.step = (self.pid.step * factor).clamp(MIN_STEP, MAX_STEP);
+    }
+
+    /// Changes the focused value by `steps` increments and publishes it
+    pub fn pid_adjust(&mut self, steps: f64) {
+        let (key, label) = FIELDS[self.pid.field];
+        let topic = format!("{}/{}", self.pid.table, key);
+        let Some(current) = self.pid_value(&self.pid.table, key) else {
+            self.set_copy_message(format!("{} has no value yet", label));
+            return;
+        };
+
+        // Snap to the step so repeated presses don't pile up float noise
+        let step = self.pid.step;
+        let value = ((current + steps * step) / step).round() * step;
+        if self.publish(topic, NtValue::Double(value)) {
+            self.set_copy_message(format!("{} = {}", label, format_gain(value)));
+        }
+    }
+
+    /// Types an exact value for the focused field
+    pub fn pid_edit_field(&mut self) {
+        let (key, _) = FIELDS[self.pid.field];
+        self.enter_value_edit_for(format!("{}/{}", self.pid.table, key));
+    }
+}
+
+/// Shows every gain, one per line when the cell is tall enough and on a single line otherwise.
+/// While tuning, the focused field is highlighted and the step is shown on the bottom border.
+pub fn render_pid(f: &mut ratatui::Frame, app: &App, widget: &Widget, area: Rect, block: Block) {
+    // Typing an exact value keeps the controller highlighted behind the popup
+    let tuning = app.pid.table == widget.topic
+        && (app.mode == Window::PidTune
+            || (app.mode == Window::ValueEdit && app.value_edit_return == Window::PidTune));
+
+    let fields: Vec<(Span, Span)> = FIELDS
+        .iter()
+        .enumerate()
+        .map(|(i, (key, label))| {
+            let value = app
+                .pid_value(&widget.topic, key)
+                .map_or("-".to_string(), format_gain);
+            let style = if tuning && i == app.pid.field {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightYellow)
+            };
+            (format!("{} ", label).bold(), Span::styled(value, style))
+        })
+        .collect();
+
+    let block = if tuning {
+        block
+            .border_style(Style::default().fg(Color::Yellow))
+            .title_bottom(Line::from(format!(" ±{} ", format_gain(app.pid.step))).right_aligned())
+    } else {
+        block
+    };
+
+    let lines: Vec<Line> = if block.inner(area).height as usize >= FIELDS.len() {
+        fields
+            .into_iter()
+            .map(|(label, value)| Line::from(vec![label, value]))
+            .collect()
+    } else {
+        let mut spans = Vec::new();
+        for (i, (label, value)) in fields.into_iter().enumerate() {
+            if i > 0 {
+                spans.push("  ".into());
+            }
+            spans.extend([label, value]);
+        }
+        vec![Line::from(spans)]
+    };
+
+    let text = Paragraph::new(lines).block(block).centered();
+    f.render_widget(text, area);
+}
+
+/// Keys for tuning, shown in place of the dashboard's help bar
+pub fn help_line() -> Line<'static> {
+    Line::from(vec![
+        "[".dim(),
+        "jk".blue().bold(),
+        "] ".dim(),
+        "Field".reset(),
+        "   [".dim(),
+        "hl".yellow().bold(),
+        "] ".dim(),
+        "Adjust".reset(),
+        "   [".dim(),
+        "HL".yellow().bold(),
+        "] ".dim(),
+        "Adjust x10".reset(),
+        "   [".dim(),
+        "[ ]".cyan().bold(),
+        "] ".dim(),
+        "Step".reset(),
+        "   [".dim(),
+        "Enter".green().bold(),
+        "] ".dim(),
+        "Type Value".reset(),
+        "   [".dim(),
+        "Esc".red().bold(),
+        "] ".dim(),
+        "Done".reset(),
+    ])
+}
+
+// Gains are often tiny, so show up to six decimals without trailing zeros
+fn format_gain(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tokio::sync::broadcast::channel;
+
+    use crate::config::Config;
+    use crate::nt::NtUpdate;
+    use crate::ui::Options;
+    use crate::value::NtType;
+
+    #[test]
+    fn adjusts_from_a_typed_value_while_paused() {
+        let (sender, mut receiver) = channel(16);
+        let options = Options {
+            read_only: false,
+            record: None,
+            record_dir: PathBuf::new(),
+            playback_control: None,
+        };
+        let mut app = App::new(sender, Config::default(), &options);
+        app.topic_types.insert("/pid/p".to_string(), NtType::Double);
+        app.values
+            .insert("/pid/p".to_string(), NtValue::Double(1.0));
+        app.paused = true;
+
+        app.enter_pid_tune("/pid".to_string());
+        app.pid.step = 0.5;
+        app.pid_adjust(1.0);
+        app.pid_edit_field();
+        app.value_edit = "2".to_string();
+        app.publish_value();
+        assert_eq!(app.mode, Window::PidTune);
+        app.pid_adjust(1.0);
+
+        let mut published = Vec::new();
+        while let Ok(update) = receiver.try_recv() {
+            if let NtUpdate::Publish(topic, value) = update {
+                assert_eq!(topic, "/pid/p");
+                published.push(value.as_f64().unwrap());
+            }
+        }
+        assert_eq!(published, vec![1.5, 2.0, 2.5]);
+        assert_eq!(app.pid_value("/pid", "p"), Some(2.5));
+    }
+}
@@ -0,0 +1,55 @@
+use ratatui::{style::Stylize, text::Line};
+
+use crate::playback::{PlaybackControl, PlaybackStatus};
+use crate::ui::Window;
+use crate::ui::app::App;
+
+impl App {
+    /// Sends a transport control to the player; does nothing when connected live
+    pub fn send_playback(&mut self, control: PlaybackControl) {
+        let Some(playback_control) = &self.playback_control else {
+            return;
+        };
+        if let Err(e) = playback_control.send(control) {
+            log::error!("Failed to send playback control: {}", e);
+        }
+        self.update_activity();
+    }
+
+    /// Forgets every topic so a seek can rebuild the dashboard from scratch
+    pub fn reset_topics(&mut self) {
+        self.values.clear();
+        self.topic_types.clear();
+        self.last_updated.clear();
+        self.available_topics.clear();
+        self.gone_topics.clear();
+        self.history.clear();
+        if self.mode == Window::FuzzySearch {
+            self.fuzzy_search.update_matches(&self.available_topics);
+        }
+    }
+}
+
+pub fn status_line(playback: &PlaybackStatus) -> Line<'static> {
+    let state = if playback.playing {
+        "▶".green().bold()
+    } else {
+        "⏸".yellow().bold()
+    };
+    Line::from(vec![
+        "Replay ".bold(),
+        state,
+        format!(
+            " {}/{} ",
+            format_time(playback.position),
+            format_time(playback.duration)
+        )
+        .reset(),
+        format!("{}x", playback.speed).cyan().bold(),
+    ])
+}
+
+fn format_time(secs: f64) -> String {
+    let secs = secs as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+use crate::nt::NtUpdate;
+use crate::recorder::{self, Recorder};
+use crate::ui::app::App;
+
+impl App {
+    pub fn toggle_recording(&mut self) {
+        if self.recorder.is_some() {
+            self.stop_recording();
+        } else {
+            let path = self
+                .record_dir
+                .join(recorder::timestamped_file_name("wpilog"));
+            self.start_recording(path);
+        }
+    }
+
+    pub fn start_recording(&mut self, path: PathBuf) {
+        let mut recorder = match Recorder::create(&path) {
+            Ok(recorder) => recorder,
+            Err(e) => {
+                log::error!("Failed to start recording to {}: {}", path.display(), e);
+                self.set_copy_message(format!("Failed to start recording: {}", e));
+                return;
+            }
+        };
+
+        // Subscribe before taking the snapshot so nothing falls in between
+        let (feed_sender, feed) = unbounded_channel();
+        tokio::spawn(forward_updates(self.sender.subscribe(), feed_sender));
+
+        // Topics announced before now won't be announced again, so log what we know
+        let snapshot = self
+            .topic_types
+            .iter()
+            .try_for_each(|(topic, ty)| recorder.start_entry(topic, ty))
+            .and_then(|_| {
+                self.values
+                    .iter()
+                    .try_for_each(|(topic, value)| recorder.record_value(topic, value))
+            });
+        if let Err(e) = snapshot {
+            log::error!("Failed to write to {}: {}", path.display(), e);
+        }
+
+        log::info!("Recording to {}", path.display());
+        self.set_copy_message(format!("Recording to {}", path.display()));
+        self.recorder = Some(recorder);
+        self.recording_feed = Some(feed);
+    }
+
+    pub fn stop_recording(&mut self) {
+        // Write what's still queued, then drop the feed to end its forwarding task
+        self.drain_recording_feed();
+        self.recording_feed = None;
+        let Some(recorder) = self.recorder.take() else {
+            return;
+        };
+        let path = recorder.path().display().to_string();
+        match recorder.finish() {
+            Ok(()) => {
+                log::info!("Saved recording to {}", path);
+                self.set_copy_message(format!("Saved recording to {}", path));
+            }
+            Err(e) => log::error!("Failed to save recording to {}: {}", path, e),
+        }
+    }
+
+    /// Writes everything the recording's own receiver has picked up since the last frame
+    pub fn drain_recording_feed(&mut self) {
+        while let Some(feed) = &mut self.recording_feed
+            && let Ok(update) = feed.try_recv()
+        {
+            match update {
+                Ok(update) => self.record_update(&update),
+                Err(RecvError::Lagged(n)) => {
+                    log::warn!("Recording fell behind and missed {} updates", n);
+                    if let Some(recorder) = &mut self.recorder {
+                        recorder.add_missed(n);
+                    }
+                }
+                Err(RecvError::Closed) => self.recording_feed = None,
+            }
+        }
+    }
+
+    /// Writes an update to the active recording, stopping it if the file can't be written
+    pub fn record_update(&mut self, update: &NtUpdate) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        if let Err(e) = recorder.record(update) {
+            log::error!("Recording to {} failed: {}", recorder.path().display(), e);
+            self.recorder = None;
+            self.recording_feed = None;
+            self.set_copy_message(format!("Recording stopped: {}", e));
+        }
+    }
+}
+
+/// Passes NT updates on to the active recording. It reads from a receiver of its own as
+/// soon as they arrive, so the recording doesn't miss any while the UI is busy drawing.
+async fn forward_updates(
+    mut updates: Receiver<NtUpdate>,
+    feed: UnboundedSender<Result<NtUpdate, RecvError>>,
+) {
+    loop {
+        let update = updates.recv().await;
+        let closed = matches!(update, Err(RecvError::Closed));
+        if feed.send(update).is_err() || closed {
+            return;
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Tabs},
+};
+
+use crate::config::Tab;
+use crate::ui::Window;
+use crate::ui::app::App;
+
+impl App {
+    pub fn select_tab(&mut self, index: usize) {
+        if index < self.config.tabs.len() {
+            self.current_tab = index;
+            self.selected_cell = None;
+        }
+    }
+
+    pub fn next_tab(&mut self) {
+        self.select_tab((self.current_tab + 1) % self.config.tabs.len());
+    }
+
+    pub fn prev_tab(&mut self) {
+        let len = self.config.tabs.len();
+        self.select_tab((self.current_tab + len - 1) % len);
+    }
+
+    /// Moves the current tab left or right in the tab bar
+    pub fn move_tab(&mut self, delta: isize) {
+        let new_index = self.current_tab as isize + delta;
+        if new_index < 0 || new_index as usize >= self.config.tabs.len() {
+            return;
+        }
+        self.config.tabs.swap(self.current_tab, new_index as usize);
+        self.current_tab = new_index as usize;
+        self.save_config();
+    }
+
+    pub fn enter_tab_new(&mut self) {
+        self.tab_name_edit.clear();
+        self.tab_name_new = true;
+        self.mode = Window::TabName;
+    }
+
+    pub fn enter_tab_rename(&mut self) {
+        self.tab_name_edit = self.config.tabs[self.current_tab].name.clone();
+        self.tab_name_new = false;
+        self.mode = Window::TabName;
+    }
+
+    pub fn exit_tab_name(&mut self) {
+        self.mode = Window::Main;
+    }
+
+    pub fn save_tab_name(&mut self) {
+        let name = self.tab_name_edit.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        if self.tab_name_new {
+            self.config.tabs.push(Tab::new(name));
+            self.select_tab(self.config.tabs.len() - 1);
+        } else {
+            self.config.tabs[self.current_tab].name = name;
+        }
+        self.save_config();
+        self.exit_tab_name();
+    }
+
+    pub fn enter_tab_delete(&mut self) {
+        if self.config.tabs.len() <= 1 {
+            self.set_copy_message("Can't delete the last tab".to_string());
+            return;
+        }
+        self.mode = Window::TabDelete;
+    }
+
+    pub fn delete_current_tab(&mut self) {
+        if self.config.tabs.len() > 1 {
+            let tab = self.config.tabs.remove(self.current_tab);
+            self.select_tab(self.current_tab.min(self.config.tabs.len() - 1));
+            self.save_config();
+            self.set_copy_message(format!("Deleted tab {}", tab.name));
+        }
+        self.mode = Window::Main;
+    }
+
+    fn save_config(&self) {
+        self.config.save().unwrap_or_else(|e| {
+            log::error!("Failed to save config: {}", e);
+        });
+    }
+}
+
+pub fn render_tab_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let titles = app
+        .config
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.name));
+
+    let tabs = Tabs::new(titles)
+        .select(app.current_tab)
+        .style(Style::default().fg(Color::DarkGray))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )
+        .divider("|");
+    f.render_widget(tabs, area);
+}
+
+pub fn render_tab_name_edit(f: &mut ratatui::Frame, app: &App, size: Rect) {
+    let popup_width = (size.width / 2).max(50);
+    let popup_height = 9; // Input box and controls
+
+    let popup_x = (size.width - popup_width) / 2;
+    let popup_y = (size.height - popup_height) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Create a clear background for the popup
+    f.render_widget(Clear, popup_area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Input box
+            Constraint::Length(5), // Controls
+        ])
+        .margin(0)
+        .split(popup_area);
+
+    let input_text = format!(
+        "{}{}",
+        app.tab_name_edit,
+        if app.cursor_visible { "_" } else { " " }
+    );
+
+    let input_box = Paragraph::new(input_text)
+        .block(
+            Block::default()
+                .title(if app.tab_name_new {
+                    "New Tab"
+                } else {
+                    "Rename Tab"
+                })
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Left);
+
+    let help_text = vec![
+        Line::from(vec![
+            "[".dim(),
+            "Enter".green().bold(),
+            "] ".dim(),
+            "Save".reset(),
+        ]),
+        Line::from(vec![
+            "[".dim(),
+            "Esc".red().bold(),
+            "] ".dim(),
+            "Cancel".reset(),
+        ]),
+    ];
+
+    let controls_box = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .title("Controls")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue))
+                .padding(Padding::new(1, 0, 0, 0)),
+        )
+        .alignment(Alignment::Left);
+
+    f.render_widget(input_box, layout[0]);
+    f.render_widget(controls_box, layout[1]);
+}
+
+pub fn render_tab_delete(f: &mut ratatui::Frame, app: &App, size: Rect) {
+    let popup_width = (size.width / 2).max(50);
+    let popup_height = 5;
+
+    let popup_x = (size.width - popup_width) / 2;
+    let popup_y = (size.height - popup_height) / 2;
+
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    // Create a clear background for the popup
+    f.render_widget(Clear, popup_area);
+
+    let tab = &app.config.tabs[app.current_tab];
+    let text = vec![
+        Line::from(format!(
+            "Delete tab \"{}\" and its {} widgets?",
+            tab.name,
+            tab.widgets.len()
+        )),
+        Line::from(vec![
+            "[".dim(),
+            "y".red().bold(),
+            "] ".dim(),
+            "Delete".reset(),
+            "   [".dim(),
+            "n".green().bold(),
+            "] ".dim(),
+            "Cancel".reset(),
+        ]),
+    ];
+
+    let confirm_box = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title("Delete Tab")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .padding(Padding::horizontal(1)),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(confirm_box, popup_area);
+}
@@ -0,0 +1,424 @@
+use rmpv::Value;
+use serde_json::json;
+use std::fmt;
+
+use crate::structs::StructValue;
+
+/// The NT4 type of a topic, parsed from the type string in its announcement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NtType {
+    Boolean,
+    Int,
+    Float,
+    Double,
+    String,
+    Json,
+    Raw,
+    BooleanArray,
+    IntArray,
+    FloatArray,
+    DoubleArray,
+    StringArray,
+    // Anything else (struct:, proto:, msgpack, rpc, ...) keeps its original type string
+    Other(String),
+}
+
+impl NtType {
+    /// Parses an NT4 type string such as "double" or "string[]"
+    pub fn from_type_str(s: &str) -> Self {
+        match s {
+            "boolean" => NtType::Boolean,
+            "int" => NtType::Int,
+            "float" => NtType::Float,
+            "double" => NtType::Double,
+            "string" => NtType::String,
+            "json" => NtType::Json,
+            "raw" => NtType::Raw,
+            "boolean[]" => NtType::BooleanArray,
+            "int[]" => NtType::IntArray,
+            "float[]" => NtType::FloatArray,
+            "double[]" => NtType::DoubleArray,
+            "string[]" => NtType::StringArray,
+            other => NtType::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            NtType::Boolean => "boolean",
+            NtType::Int => "int",
+            NtType::Float => "float",
+            NtType::Double => "double",
+            NtType::String => "string",
+            NtType::Json => "json",
+            NtType::Raw => "raw",
+            NtType::BooleanArray => "boolean[]",
+            NtType::IntArray => "int[]",
+            NtType::FloatArray => "float[]",
+            NtType::DoubleArray => "double[]",
+            NtType::StringArray => "string[]",
+            NtType::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for NtType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A decoded NetworkTables value, tagged with the type it was announced as.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NtValue {
+    Boolean(bool),
+    Int(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Json(String),
+    Raw(Vec<u8>),
+    BooleanArray(Vec<bool>),
+    IntArray(Vec<i64>),
+    FloatArray(Vec<f32>),
+    DoubleArray(Vec<f64>),
+    StringArray(Vec<String>),
+    // Values of a type we don't decode, or that didn't match their announced type
+    Other(NtType, Value),
+    /// A `struct:` or `proto:` value decoded with its schema, keeping the raw bytes for recording
+    Struct(NtType, Vec<u8>, StructValue),
+}
+
+impl NtValue {
+    /// Parses user input as a value of the given type, e.g. "true", "4.2" or "[1, 2, 3]".
+    pub fn parse(ty: &NtType, raw: &str) -> Result<Self, String> {
+        let input = raw.trim();
+        Ok(match ty {
+            NtType::Boolean => NtValue::Boolean(parse_bool(input)?),
+            NtType::Int => NtValue::Int(parse_number(input, "int")?),
+            NtType::Float => NtValue::Float(parse_number(input, "float")?),
+            NtType::Double => NtValue::Double(parse_number(input, "double")?),
+            // Strings are published as typed, surrounding spaces included
+            NtType::String => NtValue::String(raw.to_string()),
+            NtType::Json => {
+                serde_json::from_str::<serde_json::Value>(input)
+                    .map_err(|e| format!("Invalid JSON: {}", e))?;
+                NtValue::Json(input.to_string())
+            }
+            NtType::BooleanArray => NtValue::BooleanArray(parse_array(input, parse_bool)?),
+            NtType::IntArray => NtValue::IntArray(parse_array(input, |s| parse_number(s, "int"))?),
+            NtType::FloatArray => {
+                NtValue::FloatArray(parse_array(input, |s| parse_number(s, "float"))?)
+            }
+            NtType::DoubleArray => {
+                NtValue::DoubleArray(parse_array(input, |s| parse_number(s, "double"))?)
+            }
+            NtType::StringArray => NtValue::StringArray(parse_array(input, |s| Ok(unquote(s)))?),
+            NtType::Raw | NtType::Other(_) => {
+                return Err(format!("Can't publish values of type {}", ty));
+            }
+        })
+    }
+
+    /// Decodes a raw msgpack value according to the topic's announced type.
+    /// Falls back to `NtValue::Other` if the value doesn't fit the type.
+    pub fn decode(ty: &NtType, value: Value) -> Self {
+        Self::try_decode(ty, &value).unwrap_or(NtValue::Other(ty.clone(), value))
+    }
+
+    fn try_decode(ty: &NtType, value: &Value) -> Option<Self> {
+        Some(match ty {
+            NtType::Boolean => NtValue::Boolean(value.as_bool()?),
+            NtType::Int => NtValue::Int(value.as_i64()?),
+            NtType::Float => NtValue::Float(as_f64(value)? as f32),
+            NtType::Double => NtValue::Double(as_f64(value)?),
+            NtType::String => NtValue::String(value.as_str()?.to_string()),
+            NtType::Json => NtValue::Json(value.as_str()?.to_string()),
+            NtType::Raw => NtValue::Raw(value.as_slice()?.to_vec()),
+            NtType::BooleanArray => NtValue::BooleanArray(array_of(value, Value::as_bool)?),
+            NtType::IntArray => NtValue::IntArray(array_of(value, Value::as_i64)?),
+            NtType::FloatArray => {
+                NtValue::FloatArray(array_of(value, |v| as_f64(v).map(|f| f as f32))?)
+            }
+            NtType::DoubleArray => NtValue::DoubleArray(array_of(value, as_f64)?),
+            NtType::StringArray => {
+                NtValue::StringArray(array_of(value, |v| v.as_str().map(str::to_string))?)
+            }
+            NtType::Other(_) => return None,
+        })
+    }
+
+    pub fn nt_type(&self) -> NtType {
+        match self {
+            NtValue::Boolean(_) => NtType::Boolean,
+            NtValue::Int(_) => NtType::Int,
+            NtValue::Float(_) => NtType::Float,
+            NtValue::Double(_) => NtType::Double,
+            NtValue::String(_) => NtType::String,
+            NtValue::Json(_) => NtType::Json,
+            NtValue::Raw(_) => NtType::Raw,
+            NtValue::BooleanArray(_) => NtType::BooleanArray,
+            NtValue::IntArray(_) => NtType::IntArray,
+            NtValue::FloatArray(_) => NtType::FloatArray,
+            NtValue::DoubleArray(_) => NtType::DoubleArray,
+            NtValue::StringArray(_) => NtType::StringArray,
+            NtValue::Other(ty, _) | NtValue::Struct(ty, ..) => ty.clone(),
+        }
+    }
+
+    /// Encodes the value back into msgpack for publishing
+    pub fn to_rmpv(&self) -> Value {
+        match self {
+            NtValue::Boolean(b) => Value::from(*b),
+            NtValue::Int(i) => Value::from(*i),
+            NtValue::Float(f) => Value::from(*f),
+            NtValue::Double(d) => Value::from(*d),
+            NtValue::String(s) | NtValue::Json(s) => Value::from(s.as_str()),
+            NtValue::Raw(bytes) => Value::from(bytes.as_slice()),
+            NtValue::BooleanArray(v) => Value::Array(v.iter().map(|b| Value::from(*b)).collect()),
+            NtValue::IntArray(v) => Value::Array(v.iter().map(|i| Value::from(*i)).collect()),
+            NtValue::FloatArray(v) => Value::Array(v.iter().map(|f| Value::from(*f)).collect()),
+            NtValue::DoubleArray(v) => Value::Array(v.iter().map(|d| Value::from(*d)).collect()),
+            NtValue::StringArray(v) => {
+                Value::Array(v.iter().map(|s| Value::from(s.as_str())).collect())
+            }
+            NtValue::Other(_, value) => value.clone(),
+            NtValue::Struct(_, bytes, _) => Value::from(bytes.as_slice()),
+        }
+    }
+
+    /// Numeric view of the value, used by graphs
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            NtValue::Int(i) => Some(*i as f64),
+            NtValue::Float(f) => Some(*f as f64),
+            NtValue::Double(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            NtValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            NtValue::Boolean(b) => json!(b),
+            NtValue::Int(i) => json!(i),
+            NtValue::Float(f) => json!(f),
+            NtValue::Double(d) => json!(d),
+            NtValue::String(s) => json!(s),
+            // Embed JSON topics as JSON when they parse, otherwise keep the text
+            NtValue::Json(s) => serde_json::from_str(s).unwrap_or_else(|_| json!(s)),
+            NtValue::Raw(bytes) => json!(bytes),
+            NtValue::BooleanArray(v) => json!(v),
+            NtValue::IntArray(v) => json!(v),
+            NtValue::FloatArray(v) => json!(v),
+            NtValue::DoubleArray(v) => json!(v),
+            NtValue::StringArray(v) => json!(v),
+            NtValue::Other(..) => json!(self.to_string()),
+            NtValue::Struct(_, _, value) => value.to_json(),
+        }
+    }
+}
+
+impl fmt::Display for NtValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NtValue::Boolean(b) => write!(f, "{}", b),
+            NtValue::Int(i) => write!(f, "{}", i),
+            NtValue::Float(v) => write!(f, "{}", v),
+            NtValue::Double(v) => write!(f, "{}", v),
+            NtValue::String(s) | NtValue::Json(s) => write!(f, "{}", s),
+            NtValue::Raw(bytes) => write!(f, "<{} bytes>", bytes.len()),
+            NtValue::BooleanArray(v) => write_list(f, v),
+            NtValue::IntArray(v) => write_list(f, v),
+            NtValue::FloatArray(v) => write_list(f, v),
+            NtValue::DoubleArray(v) => write_list(f, v),
+            NtValue::StringArray(v) => write_list(f, v),
+            NtValue::Other(_, value) => write!(f, "{}", value.to_string().trim()),
+            NtValue::Struct(_, _, value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, "]")
+}
+
+fn parse_bool(input: &str) -> Result<bool, String> {
+    match input.to_lowercase().as_str() {
+        "true" | "t" | "1" => Ok(true),
+        "false" | "f" | "0" => Ok(false),
+        _ => Err(format!("'{}' is not a boolean (true/false)", input)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(input: &str, type_name: &str) -> Result<T, String> {
+    input
+        .parse()
+        .map_err(|_| format!("'{}' is not a valid {}", input, type_name))
+}
+
+// Accepts "1, 2, 3" as well as "[1, 2, 3]"
+fn parse_array<T>(input: &str, f: impl Fn(&str) -> Result<T, String>) -> Result<Vec<T>, String> {
+    let inner = input
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(input)
+        .trim();
+    if inner.is_empty() {
+        return Ok(Vec::new());
+    }
+    split_items(inner)?
+        .into_iter()
+        .map(|item| f(item.trim()))
+        .collect()
+}
+
+// Splits on commas outside of double quotes, so quoted strings can contain them
+fn split_items(input: &str) -> Result<Vec<&str>, String> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                items.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        return Err(format!("Unterminated quote in '{}'", input));
+    }
+    items.push(&input[start..]);
+    Ok(items)
+}
+
+// Items can be bare (`a`) or quoted (`"a, b"`), with \" and \\ escapes inside quotes
+fn unquote(item: &str) -> String {
+    let Some(inner) = item.strip_prefix('"').and_then(|s| s.strip_suffix('"')) else {
+        return item.to_string();
+    };
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+    text
+}
+
+// Doubles are sometimes sent as floats or integers, so accept any number
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::F64(d) => Some(*d),
+        Value::F32(f) => Some(*f as f64),
+        Value::Integer(i) => i.as_f64(),
+        _ => None,
+    }
+}
+
+fn array_of<T>(value: &Value, f: impl Fn(&Value) -> Option<T>) -> Option<Vec<T>> {
+    value.as_array()?.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_booleans() {
+        assert_eq!(NtValue::parse(&NtType::Int, " 42 "), Ok(NtValue::Int(42)));
+        assert_eq!(
+            NtValue::parse(&NtType::Double, "-1.5"),
+            Ok(NtValue::Double(-1.5))
+        );
+        assert_eq!(
+            NtValue::parse(&NtType::Boolean, "T"),
+            Ok(NtValue::Boolean(true))
+        );
+        assert!(NtValue::parse(&NtType::Int, "4.2").is_err());
+        assert!(NtValue::parse(&NtType::Boolean, "yes").is_err());
+    }
+
+    #[test]
+    fn keeps_spaces_in_strings() {
+        assert_eq!(
+            NtValue::parse(&NtType::String, "  two words "),
+            Ok(NtValue::String("  two words ".to_string()))
+        );
+        assert_eq!(
+            NtValue::parse(&NtType::String, ""),
+            Ok(NtValue::String(String::new()))
+        );
+    }
+
+    #[test]
+    fn validates_json() {
+        assert_eq!(
+            NtValue::parse(&NtType::Json, r#" {"a": [1, 2]} "#),
+            Ok(NtValue::Json(r#"{"a": [1, 2]}"#.to_string()))
+        );
+        assert!(NtValue::parse(&NtType::Json, "{a: 1}").is_err());
+        assert!(NtValue::parse(&NtType::Json, "").is_err());
+    }
+
+    #[test]
+    fn parses_arrays() {
+        assert_eq!(
+            NtValue::parse(&NtType::IntArray, "[1, 2, 3]"),
+            Ok(NtValue::IntArray(vec![1, 2, 3]))
+        );
+        assert_eq!(
+            NtValue::parse(&NtType::DoubleArray, "0.5,1"),
+            Ok(NtValue::DoubleArray(vec![0.5, 1.0]))
+        );
+        assert_eq!(
+            NtValue::parse(&NtType::BooleanArray, "[]"),
+            Ok(NtValue::BooleanArray(vec![]))
+        );
+        assert!(NtValue::parse(&NtType::IntArray, "[1, x]").is_err());
+    }
+
+    #[test]
+    fn parses_quoted_string_array_items() {
+        assert_eq!(
+            NtValue::parse(
+                &NtType::StringArray,
+                r#"["a, b", plain, "say \"hi\"", "back\\slash"]"#
+            ),
+            Ok(NtValue::StringArray(vec![
+                "a, b".to_string(),
+                "plain".to_string(),
+                r#"say "hi""#.to_string(),
+                r"back\slash".to_string(),
+            ]))
+        );
+        assert!(NtValue::parse(&NtType::StringArray, r#""open, ended"#).is_err());
+    }
+
+    #[test]
+    fn refuses_types_it_cant_publish() {
+        assert!(NtValue::parse(&NtType::Raw, "00").is_err());
+        assert!(NtValue::parse(&NtType::Other("struct:Pose2d".to_string()), "").is_err());
+    }
+}